@@ -0,0 +1,251 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::TokenAccount;
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_token_stacking::state::{Platform, Receipt, User};
+use solana_token_stacking::{BCDEV_DECIMALS, FCTR_DECIMALS};
+use solana_token_stacking_client::amount::{parse_amount, SOL_DECIMALS};
+use solana_token_stacking_client::{display, instructions, pda};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(
+    name = "token-stacking",
+    about = "Operate and use the token stacking platform"
+)]
+struct Opts {
+    /// RPC endpoint of the cluster.
+    #[clap(long, short = 'u', default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair that signs and pays for transactions, `~/.config/solana/id.json` by default.
+    #[clap(long, short = 'k')]
+    keypair: Option<PathBuf>,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates the platform with the signer as its authority.
+    Initialize {
+        /// Round duration in seconds.
+        #[clap(long)]
+        round_duration: u64,
+        /// Registration price in SOL.
+        #[clap(long)]
+        registration_price: String,
+    },
+    /// Starts the next round.
+    StartRound {
+        /// Starts the last round of the platform.
+        #[clap(long = "final")]
+        is_final: bool,
+    },
+    /// Deposits SOL into the platform vault.
+    AddLiquidity { sol: String },
+    /// Withdraws the platform vault after the final round.
+    Withdraw,
+    /// Registers the signer as a user.
+    Register {
+        /// Takes part in the grant program.
+        #[clap(long)]
+        grant_program: bool,
+    },
+    /// Buys FCTR for the given amount of SOL.
+    Buy { sol: String },
+    /// Sells all FCTR of the signer.
+    SellFctr,
+    /// Sells the given amount of BCDEV.
+    SellBcdev { amount: String },
+    /// Stakes all FCTR of the user for the current round.
+    Stake {
+        /// Stakes on behalf of this user, the signer has to be its operator.
+        #[clap(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Unstakes and collects the reward, returning granted FCTR to the grantors.
+    Unstake {
+        /// Unstakes on behalf of this user, the signer has to be its operator.
+        #[clap(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Grants FCTR to a confidant that allows the wallet on its trust list.
+    Grant {
+        confidant: Pubkey,
+        amount: String,
+        /// Share of the reward the grantor keeps, the platform minimum by default.
+        #[clap(long)]
+        grantor_share_bps: Option<u16>,
+    },
+    /// Claims granted FCTR back from a confidant.
+    Claim {
+        confidant: Pubkey,
+        /// Claims on behalf of this user, the signer has to be its operator.
+        #[clap(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Prints an account.
+    Show {
+        #[clap(subcommand)]
+        account: ShowCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ShowCommand {
+    Platform,
+    User { authority: Option<Pubkey> },
+    Receipt { authority: Option<Pubkey> },
+}
+
+struct Cli {
+    rpc: RpcClient,
+    payer: Keypair,
+}
+
+impl Cli {
+    fn send(&self, instruction: Instruction) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&transaction)?)
+    }
+
+    fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let data = self
+            .rpc
+            .get_account_data(address)
+            .with_context(|| format!("failed to fetch account {}", address))?;
+        T::try_deserialize(&mut data.as_slice())
+            .map_err(|e| anyhow!("failed to decode account {}: {}", address, e))
+    }
+
+    fn authority(&self, authority: Option<Pubkey>) -> Pubkey {
+        authority.unwrap_or_else(|| self.payer.pubkey())
+    }
+
+    /// Wallets of the grantors on a receipt, which only stores their `User` accounts.
+    fn grantor_authorities(&self, receipt: &Receipt) -> Result<Vec<Pubkey>> {
+        receipt
+            .grantors
+            .iter()
+            .map(|g| Ok(self.account::<User>(&g.grantor)?.authority))
+            .collect()
+    }
+
+    fn run(&self, command: Command) -> Result<()> {
+        let payer = self.payer.pubkey();
+        let instruction = match command {
+            Command::Initialize {
+                round_duration,
+                registration_price,
+            } => instructions::initialize(
+                &payer,
+                round_duration,
+                parse_amount(&registration_price, SOL_DECIMALS)?,
+            ),
+            Command::StartRound { is_final } => instructions::start_round(&payer, is_final),
+            Command::AddLiquidity { sol } => {
+                instructions::add_liquidity(&payer, parse_amount(&sol, SOL_DECIMALS)?)
+            }
+            Command::Withdraw => instructions::withdraw(&payer),
+            Command::Register { grant_program } => {
+                instructions::register_user(&payer, grant_program)
+            }
+            Command::Buy { sol } => {
+                instructions::buy_tokens(&payer, parse_amount(&sol, SOL_DECIMALS)?)
+            }
+            Command::SellFctr => instructions::sell_fctr_tokens(&payer),
+            Command::SellBcdev { amount } => {
+                instructions::sell_bcdev_tokens(&payer, parse_amount(&amount, BCDEV_DECIMALS)?)
+            }
+            Command::Stake { authority } => instructions::stake(&self.authority(authority), &payer),
+            Command::Unstake { authority } => {
+                let authority = self.authority(authority);
+                let receipt = self.account::<Receipt>(&pda::receipt(&authority))?;
+                let grantors = self.grantor_authorities(&receipt)?;
+                instructions::unstake(&authority, &payer, &grantors)
+            }
+            Command::Grant {
+                confidant,
+                amount,
+                grantor_share_bps,
+            } => {
+                let grantor_share_bps = match grantor_share_bps {
+                    Some(bps) => bps,
+                    None => {
+                        self.account::<Platform>(&pda::platform())?
+                            .min_grantor_share_bps
+                    }
+                };
+                instructions::grant_tokens(
+                    &payer,
+                    &confidant,
+                    parse_amount(&amount, FCTR_DECIMALS)?,
+                    grantor_share_bps,
+                )
+            }
+            Command::Claim {
+                confidant,
+                authority,
+            } => instructions::claim_tokens(&self.authority(authority), &payer, &confidant),
+            Command::Show { account } => return self.show(account),
+        };
+        let signature = self.send(instruction)?;
+        println!("{}", signature);
+        Ok(())
+    }
+
+    fn show(&self, account: ShowCommand) -> Result<()> {
+        let output = match account {
+            ShowCommand::Platform => {
+                let platform = self.account::<Platform>(&pda::platform())?;
+                let sol_vault_lamports = self.rpc.get_balance(&pda::sol_vault())?;
+                display::platform(&platform, sol_vault_lamports)
+            }
+            ShowCommand::User { authority } => {
+                let authority = self.authority(authority);
+                let user = self.account::<User>(&pda::user(&authority))?;
+                let fctr_vault = self.account::<TokenAccount>(&pda::fctr_vault(&authority))?;
+                let bcdev_vault = self.account::<TokenAccount>(&pda::bcdev_vault(&authority))?;
+                display::user(&user, fctr_vault.amount, bcdev_vault.amount)
+            }
+            ShowCommand::Receipt { authority } => {
+                let authority = self.authority(authority);
+                display::receipt(&self.account::<Receipt>(&pda::receipt(&authority))?)
+            }
+        };
+        print!("{}", output);
+        Ok(())
+    }
+}
+
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME is not set, pass --keypair")?;
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
+
+fn main() -> Result<()> {
+    let opts = Opts::parse();
+    let keypair_path = match opts.keypair {
+        Some(path) => path,
+        None => default_keypair_path()?,
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|e| anyhow!("failed to read keypair {}: {}", keypair_path.display(), e))?;
+    let cli = Cli {
+        rpc: RpcClient::new_with_commitment(opts.url, CommitmentConfig::confirmed()),
+        payer,
+    };
+    cli.run(opts.command)
+}
@@ -0,0 +1,42 @@
+//! Conversions between raw token units and the decimal amounts people type and read.
+use anyhow::{anyhow, bail, Result};
+
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Formats `amount` raw units with `decimals` places, dropping trailing zeros.
+pub fn format_amount(amount: u64, decimals: u8) -> String {
+    let unit = 10u64.pow(decimals as u32);
+    let fraction = format!("{:0width$}", amount % unit, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        (amount / unit).to_string()
+    } else {
+        format!("{}.{}", amount / unit, fraction)
+    }
+}
+
+/// Parses a decimal amount such as `1.5` into raw units with `decimals` places.
+pub fn parse_amount(amount: &str, decimals: u8) -> Result<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        bail!("empty amount");
+    }
+    if fraction.len() > decimals as usize {
+        bail!("{} has more than {} decimal places", amount, decimals);
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        bail!("{} is not a decimal number", amount);
+    }
+    let padded_fraction = format!("{:0<width$}", fraction, width = decimals as usize);
+    let whole = if whole.is_empty() { "0" } else { whole };
+    whole
+        .parse::<u64>()
+        .ok()
+        .and_then(|whole| whole.checked_mul(10u64.pow(decimals as u32)))
+        .and_then(|whole| whole.checked_add(padded_fraction.parse().unwrap_or(0)))
+        .ok_or_else(|| anyhow!("{} is too large", amount))
+}
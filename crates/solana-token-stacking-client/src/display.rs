@@ -0,0 +1,126 @@
+//! Human-readable renderings of the program accounts.
+use crate::amount::{format_amount, SOL_DECIMALS};
+use solana_token_stacking::state::{Platform, Receipt, User};
+use solana_token_stacking::{BCDEV_DECIMALS, FCTR_DECIMALS};
+use std::fmt::Write;
+
+fn fctr(amount: u64) -> String {
+    format!("{} FCTR", format_amount(amount, FCTR_DECIMALS))
+}
+
+fn bcdev(amount: u64) -> String {
+    format!("{} BCDEV", format_amount(amount, BCDEV_DECIMALS))
+}
+
+fn sol(lamports: u64) -> String {
+    format!("{} SOL", format_amount(lamports, SOL_DECIMALS))
+}
+
+fn percent(bps: u16) -> String {
+    format!("{}%", format_amount(bps as u64, 2))
+}
+
+pub fn platform(platform: &Platform, sol_vault_lamports: u64) -> String {
+    fields(&[
+        ("Authority", platform.authority.to_string()),
+        ("Round", platform.round_index.to_string()),
+        ("Round start", platform.round_start.to_string()),
+        ("Round duration", format!("{}s", platform.round_duration)),
+        ("Final round", platform.is_final.to_string()),
+        ("Paused", platform.is_paused.to_string()),
+        ("Registration price", sol(platform.registration_price)),
+        ("SOL vault", sol(sol_vault_lamports)),
+        ("FCTR supply", fctr(platform.fctr_token_total_amount)),
+        ("BCDEV supply", bcdev(platform.bcdev_token_total_amount)),
+        ("FCTR staked", fctr(platform.total_fctr_staked)),
+        ("FCTR granted", fctr(platform.total_fctr_granted)),
+        ("FCTR pending", fctr(platform.total_fctr_pending)),
+        ("FCTR offered", fctr(platform.total_fctr_offered)),
+        ("Active stakers", platform.active_stakers.to_string()),
+        ("BCDEV rewards", bcdev(platform.total_bcdev_rewards)),
+        ("SOL in", sol(platform.total_sol_in)),
+        ("SOL out", sol(platform.total_sol_out)),
+        (
+            "Max active grantors",
+            platform.max_active_grantors.to_string(),
+        ),
+        ("Grantor history", platform.max_grantors_history.to_string()),
+        (
+            "Grant ratio",
+            format!(
+                "{} - {}",
+                platform.min_grant_ratio, platform.max_grant_ratio
+            ),
+        ),
+        (
+            "Grantor share",
+            format!(
+                "{} - {}",
+                percent(platform.min_grantor_share_bps),
+                percent(platform.max_grantor_share_bps)
+            ),
+        ),
+        (
+            "Reclaim grace period",
+            format!("{}s", platform.reclaim_grace_period),
+        ),
+        (
+            "Grant offer TTL",
+            format!("{} rounds", platform.grant_offer_ttl_rounds),
+        ),
+    ])
+}
+
+pub fn user(user: &User, fctr_balance: u64, bcdev_balance: u64) -> String {
+    let operator = user
+        .operator
+        .map(|operator| operator.to_string())
+        .unwrap_or_else(|| "none".to_string());
+    fields(&[
+        ("Authority", user.authority.to_string()),
+        ("Operator", operator),
+        ("Grant program", user.grant_program.to_string()),
+        ("FCTR balance", fctr(fctr_balance)),
+        ("BCDEV balance", bcdev(bcdev_balance)),
+        ("FCTR owned", fctr(user.user_fctr_amount)),
+        ("Next position", user.next_position_id.to_string()),
+    ])
+}
+
+pub fn receipt(receipt: &Receipt) -> String {
+    let mut out = fields(&[
+        ("Authority", receipt.authority.to_string()),
+        ("Staked", receipt.is_valid.to_string()),
+        ("Stake time", receipt.stake_ts.to_string()),
+        ("Round ends", receipt.round_ends.to_string()),
+        ("Stake duration", format!("{}s", receipt.stake_duration)),
+        ("Deposited", fctr(receipt.amount_deposited)),
+        ("APR", receipt.apr.to_string()),
+    ]);
+    for (title, grantors) in [
+        ("Grantors", &receipt.grantors),
+        ("Next round grantors", &receipt.next_round_grantors),
+    ] {
+        let _ = writeln!(out, "{} ({}):", title, grantors.len());
+        for grantor in grantors.iter() {
+            let _ = writeln!(
+                out,
+                "  {} {} for {}s, grantor share {}",
+                grantor.grantor,
+                fctr(grantor.amount),
+                grantor.grant_duration,
+                percent(grantor.grantor_share_bps)
+            );
+        }
+    }
+    out
+}
+
+fn fields(fields: &[(&str, String)]) -> String {
+    fields
+        .iter()
+        .fold(String::new(), |mut out, (label, value)| {
+            let _ = writeln!(out, "{:<22}{}", format!("{}:", label), value);
+            out
+        })
+}
@@ -0,0 +1,659 @@
+//! Builders for every program instruction.
+//!
+//! Users are identified by their wallet (`authority`), everything else is derived with
+//! [`crate::pda`]. `signer` is the key that signs on the user's behalf, which is either the
+//! authority itself or its operator.
+use crate::pda;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_token_stacking::state::{TrustEntry, TrustListMode};
+use solana_token_stacking::{accounts, instruction};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: solana_token_stacking::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(
+    platform_authority: &Pubkey,
+    round_duration: u64,
+    registration_price: u64,
+) -> Instruction {
+    build(
+        accounts::Initialize {
+            platform: pda::platform(),
+            platform_authority: *platform_authority,
+            sol_vault: pda::sol_vault(),
+            fctr_mint: pda::fctr_mint(),
+            bcdev_mint: pda::bcdev_mint(),
+            fctr_token_vault: pda::fctr_token_vault(),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize {
+            round_duration,
+            registration_price,
+        },
+    )
+}
+
+pub fn register_user(authority: &Pubkey, participate_in_grant_program: bool) -> Instruction {
+    build(
+        accounts::RegisterUser {
+            platform: pda::platform(),
+            fctr_mint: pda::fctr_mint(),
+            bcdev_mint: pda::bcdev_mint(),
+            fctr_vault: pda::fctr_vault(authority),
+            bcdev_vault: pda::bcdev_vault(authority),
+            user: pda::user(authority),
+            receipt: pda::receipt(authority),
+            grant_history: pda::grant_history(authority),
+            reputation: pda::reputation(authority),
+            user_stats: pda::user_stats(authority),
+            trust_list: pda::trust_list(authority),
+            authority: *authority,
+            sol_vault: pda::sol_vault(),
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterUser {
+            participate_in_grant_program,
+        },
+    )
+}
+
+pub fn update_trust_list(
+    authority: &Pubkey,
+    mode: TrustListMode,
+    entries: Vec<TrustEntry>,
+) -> Instruction {
+    build(
+        accounts::UpdateTrustList {
+            user: pda::user(authority),
+            trust_list: pda::trust_list(authority),
+            authority: *authority,
+            platform: pda::platform(),
+        },
+        instruction::UpdateTrustList { mode, entries },
+    )
+}
+
+pub fn set_operator(authority: &Pubkey, operator: Option<Pubkey>) -> Instruction {
+    build(
+        accounts::SetOperator {
+            user: pda::user(authority),
+            authority: *authority,
+            platform: pda::platform(),
+        },
+        instruction::SetOperator { operator },
+    )
+}
+
+pub fn audit_platform(auditor: &Pubkey, auto_pause: bool) -> Instruction {
+    build(
+        accounts::AuditPlatform {
+            platform: pda::platform(),
+            fctr_mint: pda::fctr_mint(),
+            bcdev_mint: pda::bcdev_mint(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            sol_vault: pda::sol_vault(),
+            auditor: *auditor,
+        },
+        instruction::AuditPlatform { auto_pause },
+    )
+}
+
+pub fn set_paused(platform_authority: &Pubkey, is_paused: bool) -> Instruction {
+    build(
+        accounts::SetPaused {
+            platform: pda::platform(),
+            authority: *platform_authority,
+        },
+        instruction::SetPaused { is_paused },
+    )
+}
+
+pub fn start_round(platform_authority: &Pubkey, is_final: bool) -> Instruction {
+    build(
+        accounts::StartRound {
+            platform: pda::platform(),
+            authority: *platform_authority,
+            system_program: system_program::ID,
+        },
+        instruction::StartRound { is_final },
+    )
+}
+
+pub fn update_grant_limits(
+    platform_authority: &Pubkey,
+    max_active_grantors: u32,
+    max_grantors_history: u32,
+    min_grant_ratio: f64,
+    max_grant_ratio: f64,
+) -> Instruction {
+    build(
+        accounts::UpdateGrantLimits {
+            platform: pda::platform(),
+            authority: *platform_authority,
+        },
+        instruction::UpdateGrantLimits {
+            max_active_grantors,
+            max_grantors_history,
+            min_grant_ratio,
+            max_grant_ratio,
+        },
+    )
+}
+
+/// Grows the receipt and grant history of `authority` after the grant limits were raised.
+pub fn resize_receipt(payer: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::ResizeReceipt {
+            platform: pda::platform(),
+            user: pda::user(authority),
+            receipt: pda::receipt(authority),
+            grant_history: pda::grant_history(authority),
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::ResizeReceipt {},
+    )
+}
+
+/// Grows a platform of the first program version to the current layout.
+pub fn migrate_platform(platform_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::MigratePlatform {
+            platform: pda::platform(),
+            sol_vault: pda::sol_vault(),
+            authority: *platform_authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigratePlatform {},
+    )
+}
+
+/// Brings the accounts of a user of the first program version to the current layout,
+/// `payer` pays for the accounts that have to be created.
+pub fn migrate_user(payer: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateUser {
+            platform: pda::platform(),
+            user: pda::user(authority),
+            receipt: pda::receipt(authority),
+            grant_history: pda::grant_history(authority),
+            reputation: pda::reputation(authority),
+            user_stats: pda::user_stats(authority),
+            trust_list: pda::trust_list(authority),
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateUser {},
+    )
+}
+
+pub fn update_reclaim_grace_period(platform_authority: &Pubkey, grace_period: u64) -> Instruction {
+    build(
+        accounts::UpdateReclaimGracePeriod {
+            platform: pda::platform(),
+            authority: *platform_authority,
+        },
+        instruction::UpdateReclaimGracePeriod { grace_period },
+    )
+}
+
+pub fn update_grant_offer_ttl(platform_authority: &Pubkey, ttl_rounds: u64) -> Instruction {
+    build(
+        accounts::UpdateGrantOfferTtl {
+            platform: pda::platform(),
+            authority: *platform_authority,
+        },
+        instruction::UpdateGrantOfferTtl { ttl_rounds },
+    )
+}
+
+pub fn update_grantor_share_bounds(
+    platform_authority: &Pubkey,
+    min_grantor_share_bps: u16,
+    max_grantor_share_bps: u16,
+) -> Instruction {
+    build(
+        accounts::UpdateGrantorShareBounds {
+            platform: pda::platform(),
+            authority: *platform_authority,
+        },
+        instruction::UpdateGrantorShareBounds {
+            min_grantor_share_bps,
+            max_grantor_share_bps,
+        },
+    )
+}
+
+pub fn withdraw(platform_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::Withdraw {
+            sol_vault: pda::sol_vault(),
+            platform: pda::platform(),
+            authority: *platform_authority,
+            fctr_token_vault: pda::fctr_token_vault(),
+            system_program: system_program::ID,
+        },
+        instruction::Withdraw {},
+    )
+}
+
+pub fn add_liquidity(platform_authority: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::AddLiquidity {
+            sol_vault: pda::sol_vault(),
+            platform: pda::platform(),
+            authority: *platform_authority,
+            system_program: system_program::ID,
+        },
+        instruction::AddLiquidity { amount },
+    )
+}
+
+pub fn buy_tokens(authority: &Pubkey, lamports: u64) -> Instruction {
+    build(
+        accounts::BuyTokens {
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            user_stats: pda::user_stats(authority),
+            sol_vault: pda::sol_vault(),
+            platform: pda::platform(),
+            fctr_mint: pda::fctr_mint(),
+            authority: *authority,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::BuyTokens {
+            token_amount: lamports,
+        },
+    )
+}
+
+pub fn sell_fctr_tokens(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::SellFctrTokens {
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            user_stats: pda::user_stats(authority),
+            sol_vault: pda::sol_vault(),
+            platform: pda::platform(),
+            fctr_mint: pda::fctr_mint(),
+            authority: *authority,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::SellFctrTokens {},
+    )
+}
+
+pub fn sell_bcdev_tokens(authority: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::SellBcdevTokens {
+            user: pda::user(authority),
+            bcdev_vault: pda::bcdev_vault(authority),
+            user_stats: pda::user_stats(authority),
+            sol_vault: pda::sol_vault(),
+            platform: pda::platform(),
+            bcdev_mint: pda::bcdev_mint(),
+            authority: *authority,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::SellBcdevTokens { amount },
+    )
+}
+
+pub fn stake(authority: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::Stake {
+            receipt: pda::receipt(authority),
+            user_stats: pda::user_stats(authority),
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            authority: *authority,
+            signer: *signer,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::Stake {},
+    )
+}
+
+/// `grantors` are the wallets of every grantor on the receipt. Each of them is passed as
+/// four remaining accounts: its `User` account, FCTR vault, BCDEV vault and statistics.
+pub fn unstake(authority: &Pubkey, signer: &Pubkey, grantors: &[Pubkey]) -> Instruction {
+    let mut instruction = build(
+        accounts::Unstake {
+            receipt: pda::receipt(authority),
+            grant_history: pda::grant_history(authority),
+            reputation: pda::reputation(authority),
+            user_stats: pda::user_stats(authority),
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            bcdev_vault: pda::bcdev_vault(authority),
+            authority: *authority,
+            signer: *signer,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            bcdev_mint: pda::bcdev_mint(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::Unstake {},
+    );
+    instruction
+        .accounts
+        .extend(grantors.iter().flat_map(grantor_accounts));
+    instruction
+}
+
+fn grantor_accounts(grantor: &Pubkey) -> [AccountMeta; 4] {
+    [
+        AccountMeta::new_readonly(pda::user(grantor), false),
+        AccountMeta::new(pda::fctr_vault(grantor), false),
+        AccountMeta::new(pda::bcdev_vault(grantor), false),
+        AccountMeta::new(pda::user_stats(grantor), false),
+    ]
+}
+
+pub fn grant_tokens(
+    authority: &Pubkey,
+    confidant: &Pubkey,
+    amount: u64,
+    grantor_share_bps: u16,
+) -> Instruction {
+    build(
+        accounts::GrantTokens {
+            receipt: pda::receipt(authority),
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            authority: *authority,
+            user_stats: pda::user_stats(authority),
+            confidant_user_stats: pda::user_stats(confidant),
+            confidant_user: pda::user(confidant),
+            confidant_receipt: pda::receipt(confidant),
+            confidant_authority: *confidant,
+            confidant_grant_history: pda::grant_history(confidant),
+            confidant_reputation: pda::reputation(confidant),
+            trust_list: pda::trust_list(authority),
+            confidant_trust_list: pda::trust_list(confidant),
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::GrantTokens {
+            amount,
+            grantor_share_bps,
+        },
+    )
+}
+
+pub fn offer_grant(
+    authority: &Pubkey,
+    confidant: &Pubkey,
+    amount: u64,
+    grantor_share_bps: u16,
+) -> Instruction {
+    build(
+        accounts::OfferGrant {
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            authority: *authority,
+            confidant_user: pda::user(confidant),
+            confidant_authority: *confidant,
+            trust_list: pda::trust_list(authority),
+            confidant_trust_list: pda::trust_list(confidant),
+            grant_offer: pda::grant_offer(authority, confidant),
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::OfferGrant {
+            amount,
+            grantor_share_bps,
+        },
+    )
+}
+
+/// Accepted by the confidant `authority`.
+pub fn accept_grant(authority: &Pubkey, grantor: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptGrant {
+            user: pda::user(authority),
+            receipt: pda::receipt(authority),
+            grant_history: pda::grant_history(authority),
+            reputation: pda::reputation(authority),
+            authority: *authority,
+            grantor_user: pda::user(grantor),
+            grantor_receipt: pda::receipt(grantor),
+            user_stats: pda::user_stats(authority),
+            grantor_user_stats: pda::user_stats(grantor),
+            grantor_fctr_vault: pda::fctr_vault(grantor),
+            grantor_authority: *grantor,
+            grant_offer: pda::grant_offer(grantor, authority),
+            trust_list: pda::trust_list(authority),
+            grantor_trust_list: pda::trust_list(grantor),
+            platform: pda::platform(),
+        },
+        instruction::AcceptGrant {},
+    )
+}
+
+/// `signer` is the confidant, who can reject the offer at any time, or the grantor, who can
+/// only take it back once it expired.
+pub fn reject_grant(grantor: &Pubkey, confidant: &Pubkey, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::RejectGrant {
+            grantor_user: pda::user(grantor),
+            grantor_fctr_vault: pda::fctr_vault(grantor),
+            grantor_authority: *grantor,
+            confidant_user: pda::user(confidant),
+            confidant_authority: *confidant,
+            grant_offer: pda::grant_offer(grantor, confidant),
+            signer: *signer,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::RejectGrant {},
+    )
+}
+
+pub fn revoke_grant(authority: &Pubkey, confidant: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeGrant {
+            receipt: pda::receipt(authority),
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            authority: *authority,
+            confidant_user: pda::user(confidant),
+            confidant_receipt: pda::receipt(confidant),
+            confidant_grant_history: pda::grant_history(confidant),
+            confidant_reputation: pda::reputation(confidant),
+            confidant_authority: *confidant,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::RevokeGrant {},
+    )
+}
+
+pub fn reclaim_grant(authority: &Pubkey, confidant: &Pubkey) -> Instruction {
+    build(
+        accounts::ReclaimGrant {
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            bcdev_vault: pda::bcdev_vault(authority),
+            user_stats: pda::user_stats(authority),
+            authority: *authority,
+            confidant_user: pda::user(confidant),
+            confidant_receipt: pda::receipt(confidant),
+            confidant_reputation: pda::reputation(confidant),
+            confidant_authority: *confidant,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            bcdev_mint: pda::bcdev_mint(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::ReclaimGrant {},
+    )
+}
+
+pub fn claim_tokens(authority: &Pubkey, signer: &Pubkey, confidant: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimTokens {
+            receipt: pda::receipt(authority),
+            user_stats: pda::user_stats(authority),
+            user: pda::user(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            authority: *authority,
+            signer: *signer,
+            confidant_user: pda::user(confidant),
+            confidant_receipt: pda::receipt(confidant),
+            confidant_authority: *confidant,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::ClaimTokens {},
+    )
+}
+
+/// `position_id` has to be the user's current `next_position_id`.
+pub fn open_position(
+    authority: &Pubkey,
+    position_id: u64,
+    amount: u64,
+    lock_rounds: u8,
+) -> Instruction {
+    build(
+        accounts::OpenPosition {
+            user: pda::user(authority),
+            position: pda::position(authority, position_id),
+            fctr_vault: pda::fctr_vault(authority),
+            authority: *authority,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::OpenPosition {
+            amount,
+            lock_rounds,
+        },
+    )
+}
+
+pub fn close_position(authority: &Pubkey, position_id: u64) -> Instruction {
+    build(
+        accounts::ClosePosition {
+            user: pda::user(authority),
+            position: pda::position(authority, position_id),
+            fctr_vault: pda::fctr_vault(authority),
+            bcdev_vault: pda::bcdev_vault(authority),
+            user_stats: pda::user_stats(authority),
+            authority: *authority,
+            platform: pda::platform(),
+            platform_fctr_token_vault: pda::fctr_token_vault(),
+            bcdev_mint: pda::bcdev_mint(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::ClosePosition {},
+    )
+}
+
+/// `round_index` has to be the platform's current round.
+pub fn snapshot(payer: &Pubkey, authority: &Pubkey, round_index: u64) -> Instruction {
+    build(
+        accounts::SnapshotVotingWeight {
+            platform: pda::platform(),
+            snapshot: pda::snapshot(round_index),
+            voting_weight: pda::voting_weight(round_index, authority),
+            user: pda::user(authority),
+            receipt: pda::receipt(authority),
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::Snapshot {},
+    )
+}
+
+pub fn prove_voting_weight(authority: &Pubkey, round_index: u64) -> Instruction {
+    build(
+        accounts::ProveVotingWeight {
+            snapshot: pda::snapshot(round_index),
+            voting_weight: pda::voting_weight(round_index, authority),
+            authority: *authority,
+        },
+        instruction::ProveVotingWeight { round_index },
+    )
+}
+
+pub fn quote_buy(lamports: u64) -> Instruction {
+    build(accounts::QuoteTokens {}, instruction::QuoteBuy { lamports })
+}
+
+pub fn quote_sell_fctr(amount: u64) -> Instruction {
+    build(
+        accounts::QuoteTokens {},
+        instruction::QuoteSellFctr { amount },
+    )
+}
+
+pub fn quote_sell_bcdev(amount: u64) -> Instruction {
+    build(
+        accounts::QuoteTokens {},
+        instruction::QuoteSellBcdev { amount },
+    )
+}
+
+pub fn project_reward(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::ProjectReward {
+            receipt: pda::receipt(authority),
+            user: pda::user(authority),
+            authority: *authority,
+            platform: pda::platform(),
+        },
+        instruction::ProjectReward {},
+    )
+}
+
+pub fn user_summary(authority: &Pubkey) -> Instruction {
+    build(
+        accounts::GetUserSummary {
+            user: pda::user(authority),
+            receipt: pda::receipt(authority),
+            fctr_vault: pda::fctr_vault(authority),
+            bcdev_vault: pda::bcdev_vault(authority),
+            reputation: pda::reputation(authority),
+            user_stats: pda::user_stats(authority),
+            authority: *authority,
+            platform: pda::platform(),
+        },
+        instruction::UserSummary {},
+    )
+}
@@ -0,0 +1,13 @@
+//! Client side helpers for the `solana-token-stacking` program.
+//!
+//! [`pda`] derives the address of every program account and [`instructions`] builds each
+//! instruction with its accounts in the order the program expects them. [`amount`] converts
+//! token amounts to and from their decimal form and [`display`] renders the accounts for
+//! people to read.
+
+pub mod amount;
+pub mod display;
+pub mod instructions;
+pub mod pda;
+
+pub use solana_token_stacking::ID;
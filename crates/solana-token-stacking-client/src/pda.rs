@@ -0,0 +1,82 @@
+//! Addresses of the program derived accounts.
+//!
+//! Per-user accounts are derived from the user's wallet (`authority`), not from the `User`
+//! account.
+use anchor_lang::prelude::Pubkey;
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &solana_token_stacking::ID).0
+}
+
+pub fn platform() -> Pubkey {
+    find(&[b"platform"])
+}
+
+pub fn sol_vault() -> Pubkey {
+    find(&[b"sol_vault"])
+}
+
+pub fn fctr_mint() -> Pubkey {
+    find(&[b"fctr_mint"])
+}
+
+pub fn bcdev_mint() -> Pubkey {
+    find(&[b"bcdev_mint"])
+}
+
+/// The platform's FCTR vault holding staked, granted and position tokens.
+pub fn fctr_token_vault() -> Pubkey {
+    find(&[b"fctr_token_vault"])
+}
+
+pub fn user(authority: &Pubkey) -> Pubkey {
+    find(&[b"user", authority.as_ref()])
+}
+
+pub fn receipt(authority: &Pubkey) -> Pubkey {
+    find(&[b"receipt", authority.as_ref()])
+}
+
+pub fn fctr_vault(authority: &Pubkey) -> Pubkey {
+    find(&[b"fctr_vault", authority.as_ref()])
+}
+
+pub fn bcdev_vault(authority: &Pubkey) -> Pubkey {
+    find(&[b"bcdev_vault", authority.as_ref()])
+}
+
+pub fn grant_history(authority: &Pubkey) -> Pubkey {
+    find(&[b"grant_history", authority.as_ref()])
+}
+
+pub fn reputation(authority: &Pubkey) -> Pubkey {
+    find(&[b"reputation", authority.as_ref()])
+}
+
+pub fn user_stats(authority: &Pubkey) -> Pubkey {
+    find(&[b"user_stats", authority.as_ref()])
+}
+
+pub fn trust_list(authority: &Pubkey) -> Pubkey {
+    find(&[b"trust_list", authority.as_ref()])
+}
+
+pub fn grant_offer(grantor: &Pubkey, confidant: &Pubkey) -> Pubkey {
+    find(&[b"grant_offer", grantor.as_ref(), confidant.as_ref()])
+}
+
+pub fn position(authority: &Pubkey, position_id: u64) -> Pubkey {
+    find(&[b"position", authority.as_ref(), &position_id.to_le_bytes()])
+}
+
+pub fn snapshot(round_index: u64) -> Pubkey {
+    find(&[b"snapshot", &round_index.to_le_bytes()])
+}
+
+pub fn voting_weight(round_index: u64, authority: &Pubkey) -> Pubkey {
+    find(&[
+        b"voting_weight",
+        &round_index.to_le_bytes(),
+        authority.as_ref(),
+    ])
+}
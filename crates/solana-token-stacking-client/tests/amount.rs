@@ -0,0 +1,41 @@
+use solana_token_stacking::{BCDEV_DECIMALS, FCTR_DECIMALS};
+use solana_token_stacking_client::amount::{format_amount, parse_amount, SOL_DECIMALS};
+
+#[test]
+fn formats_with_token_decimals() {
+    assert_eq!(format_amount(1_090_000, FCTR_DECIMALS), "0.00000109");
+    assert_eq!(format_amount(2_000_000_000_000, FCTR_DECIMALS), "2");
+    assert_eq!(
+        format_amount(1_500_000_000_000_000_000, BCDEV_DECIMALS),
+        "1.5"
+    );
+    assert_eq!(format_amount(0, SOL_DECIMALS), "0");
+}
+
+#[test]
+fn parses_decimal_amounts() {
+    assert_eq!(parse_amount("1.5", SOL_DECIMALS).unwrap(), 1_500_000_000);
+    assert_eq!(parse_amount(".25", FCTR_DECIMALS).unwrap(), 250_000_000_000);
+    assert_eq!(parse_amount("3", 0).unwrap(), 3);
+    assert_eq!(
+        parse_amount("18.446744073709551615", BCDEV_DECIMALS).unwrap(),
+        u64::MAX
+    );
+}
+
+#[test]
+fn rejects_invalid_amounts() {
+    assert!(parse_amount("", SOL_DECIMALS).is_err());
+    assert!(parse_amount("1.0000000001", SOL_DECIMALS).is_err());
+    assert!(parse_amount("-1", SOL_DECIMALS).is_err());
+    assert!(parse_amount("1e3", SOL_DECIMALS).is_err());
+    assert!(parse_amount("18.446744073709551616", BCDEV_DECIMALS).is_err());
+}
+
+#[test]
+fn round_trips_formatted_amounts() {
+    [0, 1, 999, 1_090_000, u64::MAX].iter().for_each(|&amount| {
+        let formatted = format_amount(amount, BCDEV_DECIMALS);
+        assert_eq!(parse_amount(&formatted, BCDEV_DECIMALS).unwrap(), amount);
+    });
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hash;
+use solana_token_stacking_client::{instructions, pda};
+
+fn sighash(name: &str) -> [u8; 8] {
+    let mut sighash = [0u8; 8];
+    sighash.copy_from_slice(&hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]);
+    sighash
+}
+
+#[test]
+fn builds_instruction_data_with_anchor_sighash() {
+    let authority = Pubkey::new_unique();
+    let instruction = instructions::buy_tokens(&authority, 10);
+
+    assert_eq!(instruction.program_id, solana_token_stacking::ID);
+    assert_eq!(instruction.data[..8], sighash("buy_tokens"));
+    assert_eq!(instruction.data[8..], 10u64.to_le_bytes());
+}
+
+#[test]
+fn signs_with_the_operator_only() {
+    let authority = Pubkey::new_unique();
+    let operator = Pubkey::new_unique();
+    let instruction = instructions::stake(&authority, &operator);
+
+    let signers = instruction
+        .accounts
+        .iter()
+        .filter(|meta| meta.is_signer)
+        .map(|meta| meta.pubkey)
+        .collect::<Vec<_>>();
+    assert_eq!(signers, vec![operator]);
+    assert_eq!(instruction.accounts[0].pubkey, pda::receipt(&authority));
+    assert!(instruction.accounts[0].is_writable);
+}
+
+#[test]
+fn appends_four_accounts_per_grantor_to_unstake() {
+    let authority = Pubkey::new_unique();
+    let grantors = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let instruction = instructions::unstake(&authority, &authority, &grantors);
+    let fixed_accounts = instructions::unstake(&authority, &authority, &[])
+        .accounts
+        .len();
+
+    let remaining = &instruction.accounts[fixed_accounts..];
+    assert_eq!(remaining.len(), 4 * grantors.len());
+    remaining
+        .chunks_exact(4)
+        .zip(grantors.iter())
+        .for_each(|(accounts, grantor)| {
+            let keys = accounts.iter().map(|meta| meta.pubkey).collect::<Vec<_>>();
+            assert_eq!(
+                keys,
+                vec![
+                    pda::user(grantor),
+                    pda::fctr_vault(grantor),
+                    pda::bcdev_vault(grantor),
+                    pda::user_stats(grantor),
+                ]
+            );
+            assert!(!accounts[0].is_writable);
+            assert!(accounts[1..].iter().all(|meta| meta.is_writable));
+            assert!(accounts.iter().all(|meta| !meta.is_signer));
+        });
+}
+
+#[test]
+fn derives_grant_offers_from_grantor_then_confidant() {
+    let grantor = Pubkey::new_unique();
+    let confidant = Pubkey::new_unique();
+    let expected = Pubkey::find_program_address(
+        &[b"grant_offer", grantor.as_ref(), confidant.as_ref()],
+        &solana_token_stacking::ID,
+    )
+    .0;
+
+    assert_eq!(pda::grant_offer(&grantor, &confidant), expected);
+    assert_ne!(pda::grant_offer(&confidant, &grantor), expected);
+    let accept = instructions::accept_grant(&confidant, &grantor);
+    assert!(accept.accounts.iter().any(|meta| meta.pubkey == expected));
+}
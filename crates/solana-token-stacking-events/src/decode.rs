@@ -0,0 +1,204 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use solana_token_stacking::events::*;
+use std::fmt;
+
+/// Prefix of the log line `sol_log_data` produces for an emitted event.
+pub const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Any event emitted by the program.
+pub enum StackingEvent {
+    PlatformInitialize(PlatformInitializeEvent),
+    UserRegistered(UserRegisteredEvent),
+    OperatorUpdated(OperatorUpdatedEvent),
+    TrustListUpdated(TrustListUpdatedEvent),
+    Withdraw(WithdrawEvent),
+    BuyFctrTokens(BuyFctrTokensEvent),
+    SellFctrTokens(SellFctrTokensEvent),
+    SellBcdevTokens(SellBcdevTokensEvent),
+    RoundStart(RoundStartEvent),
+    GrantLimitsUpdated(GrantLimitsUpdatedEvent),
+    ReclaimGracePeriodUpdated(ReclaimGracePeriodUpdatedEvent),
+    GrantOfferTtlUpdated(GrantOfferTtlUpdatedEvent),
+    GrantorShareBoundsUpdated(GrantorShareBoundsUpdatedEvent),
+    AuditReport(AuditReportEvent),
+    Paused(PausedEvent),
+    ReceiptResized(ReceiptResizedEvent),
+    PlatformMigrated(PlatformMigratedEvent),
+    UserMigrated(UserMigratedEvent),
+    LiquidityAdded(LiquidityAddedEvent),
+    Stake(StakeEvent),
+    Unstake(UnstakeEvent),
+    Grant(GrantEvent),
+    GrantOffer(GrantOfferEvent),
+    GrantOfferRejected(GrantOfferRejectedEvent),
+    RevokeGrant(RevokeGrantEvent),
+    ReclaimGrant(ReclaimGrantEvent),
+    Claim(ClaimEvent),
+    PositionOpened(PositionOpenedEvent),
+    PositionClosed(PositionClosedEvent),
+    Snapshot(SnapshotEvent),
+}
+
+impl StackingEvent {
+    /// Header shared by all events.
+    pub fn header(&self) -> &EventHeader {
+        match self {
+            StackingEvent::PlatformInitialize(e) => &e.header,
+            StackingEvent::UserRegistered(e) => &e.header,
+            StackingEvent::OperatorUpdated(e) => &e.header,
+            StackingEvent::TrustListUpdated(e) => &e.header,
+            StackingEvent::Withdraw(e) => &e.header,
+            StackingEvent::BuyFctrTokens(e) => &e.header,
+            StackingEvent::SellFctrTokens(e) => &e.header,
+            StackingEvent::SellBcdevTokens(e) => &e.header,
+            StackingEvent::RoundStart(e) => &e.header,
+            StackingEvent::GrantLimitsUpdated(e) => &e.header,
+            StackingEvent::ReclaimGracePeriodUpdated(e) => &e.header,
+            StackingEvent::GrantOfferTtlUpdated(e) => &e.header,
+            StackingEvent::GrantorShareBoundsUpdated(e) => &e.header,
+            StackingEvent::AuditReport(e) => &e.header,
+            StackingEvent::Paused(e) => &e.header,
+            StackingEvent::ReceiptResized(e) => &e.header,
+            StackingEvent::PlatformMigrated(e) => &e.header,
+            StackingEvent::UserMigrated(e) => &e.header,
+            StackingEvent::LiquidityAdded(e) => &e.header,
+            StackingEvent::Stake(e) => &e.header,
+            StackingEvent::Unstake(e) => &e.header,
+            StackingEvent::Grant(e) => &e.header,
+            StackingEvent::GrantOffer(e) => &e.header,
+            StackingEvent::GrantOfferRejected(e) => &e.header,
+            StackingEvent::RevokeGrant(e) => &e.header,
+            StackingEvent::ReclaimGrant(e) => &e.header,
+            StackingEvent::Claim(e) => &e.header,
+            StackingEvent::PositionOpened(e) => &e.header,
+            StackingEvent::PositionClosed(e) => &e.header,
+            StackingEvent::Snapshot(e) => &e.header,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    Base64(base64::DecodeError),
+    Deserialize(std::io::Error),
+    UnsupportedVersion(u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Base64(e) => write!(f, "invalid base64 event data: {}", e),
+            DecodeError::Deserialize(e) => write!(f, "invalid event layout: {}", e),
+            DecodeError::UnsupportedVersion(v) => write!(f, "unsupported event version {}", v),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+fn deserialize<T: AnchorDeserialize>(mut data: &[u8]) -> Result<T, DecodeError> {
+    T::deserialize(&mut data).map_err(DecodeError::Deserialize)
+}
+
+/// Decodes raw event data (discriminator followed by the Borsh payload).
+/// Data of events that don't belong to this program is skipped with `Ok(None)`.
+pub fn decode_event(data: &[u8]) -> Result<Option<StackingEvent>, DecodeError> {
+    if data.len() < 8 {
+        return Ok(None);
+    }
+    let (discriminator, payload) = data.split_at(8);
+    macro_rules! decode {
+        ($($event:ident => $variant:ident),* $(,)?) => {
+            $(
+                if discriminator == $event::discriminator() {
+                    return check_version(StackingEvent::$variant(deserialize(payload)?)).map(Some);
+                }
+            )*
+        };
+    }
+    decode!(
+        PlatformInitializeEvent => PlatformInitialize,
+        UserRegisteredEvent => UserRegistered,
+        OperatorUpdatedEvent => OperatorUpdated,
+        TrustListUpdatedEvent => TrustListUpdated,
+        WithdrawEvent => Withdraw,
+        BuyFctrTokensEvent => BuyFctrTokens,
+        SellFctrTokensEvent => SellFctrTokens,
+        SellBcdevTokensEvent => SellBcdevTokens,
+        RoundStartEvent => RoundStart,
+        GrantLimitsUpdatedEvent => GrantLimitsUpdated,
+        ReclaimGracePeriodUpdatedEvent => ReclaimGracePeriodUpdated,
+        GrantOfferTtlUpdatedEvent => GrantOfferTtlUpdated,
+        GrantorShareBoundsUpdatedEvent => GrantorShareBoundsUpdated,
+        AuditReportEvent => AuditReport,
+        PausedEvent => Paused,
+        ReceiptResizedEvent => ReceiptResized,
+        PlatformMigratedEvent => PlatformMigrated,
+        UserMigratedEvent => UserMigrated,
+        LiquidityAddedEvent => LiquidityAdded,
+        StakeEvent => Stake,
+        UnstakeEvent => Unstake,
+        GrantEvent => Grant,
+        GrantOfferEvent => GrantOffer,
+        GrantOfferRejectedEvent => GrantOfferRejected,
+        RevokeGrantEvent => RevokeGrant,
+        ReclaimGrantEvent => ReclaimGrant,
+        ClaimEvent => Claim,
+        PositionOpenedEvent => PositionOpened,
+        PositionClosedEvent => PositionClosed,
+        SnapshotEvent => Snapshot,
+    );
+    Ok(None)
+}
+
+fn check_version(event: StackingEvent) -> Result<StackingEvent, DecodeError> {
+    match event.header().version {
+        EVENT_SCHEMA_VERSION => Ok(event),
+        version => Err(DecodeError::UnsupportedVersion(version)),
+    }
+}
+
+/// Decodes a single log line. Lines other than `Program data: ...` are skipped with `Ok(None)`.
+pub fn parse_log_line(line: &str) -> Result<Option<StackingEvent>, DecodeError> {
+    match line.trim().strip_prefix(PROGRAM_DATA_PREFIX) {
+        Some(encoded) => decode_event(&base64::decode(encoded).map_err(DecodeError::Base64)?),
+        None => Ok(None),
+    }
+}
+
+/// Decodes all events of this program found in the log lines, in order.
+///
+/// Only `Program data: ...` lines logged while this program is executing are decoded, going by
+/// the `Program <id> invoke [n]` and `Program <id> success` / `failed` lines around them. Lines
+/// other programs log, including data with several fields or that isn't base64, are skipped.
+pub fn parse_logs<'a>(
+    lines: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<StackingEvent>, DecodeError> {
+    let program_id = solana_token_stacking::ID.to_string();
+    // Whether each program on the invocation stack is this one.
+    let mut invoked = Vec::new();
+    let mut events = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if let Some(encoded) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if invoked.last() != Some(&true) || encoded.contains(' ') {
+                continue;
+            }
+            if let Ok(data) = base64::decode(encoded) {
+                events.extend(decode_event(&data)?);
+            }
+            continue;
+        }
+        let mut words = line.split(' ');
+        match (words.next(), words.next(), words.next()) {
+            (Some("Program"), Some(id), Some("invoke")) if !id.ends_with(':') => {
+                invoked.push(id == program_id)
+            }
+            (Some("Program"), Some(id), Some("success" | "failed:")) if !id.ends_with(':') => {
+                invoked.pop();
+            }
+            _ => {}
+        }
+    }
+    Ok(events)
+}
@@ -0,0 +1,101 @@
+use crate::decode::StackingEvent;
+use anchor_lang::prelude::Pubkey;
+use std::collections::BTreeMap;
+
+/// Running FCTR and BCDEV totals of a single user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UserLedger {
+    pub lamports_spent: u64,
+    pub lamports_received: u64,
+    pub fctr_bought: u64,
+    pub fctr_sold: u64,
+    pub bcdev_sold: u64,
+    pub staked: u64,
+    pub unstaked: u64,
+    pub granted: u64,
+    pub grants_received: u64,
+    pub grants_returned: u64,
+    pub claimed: u64,
+    pub rewards: u64,
+}
+
+/// Ledgers of all users, keyed by the user's `User` account address because that's how
+/// grantors and confidants are referred to in grant events.
+#[derive(Clone, Debug, Default)]
+pub struct Ledgers {
+    pub users: BTreeMap<Pubkey, UserLedger>,
+}
+
+/// Address of the `User` account owned by `authority`.
+pub fn user_address(authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"user", authority.as_ref()], &solana_token_stacking::ID).0
+}
+
+impl Ledgers {
+    pub fn from_events<'a>(events: impl IntoIterator<Item = &'a StackingEvent>) -> Self {
+        let mut ledgers = Ledgers::default();
+        events.into_iter().for_each(|event| ledgers.apply(event));
+        ledgers
+    }
+
+    pub fn get(&self, user: &Pubkey) -> Option<&UserLedger> {
+        self.users.get(user)
+    }
+
+    fn entry(&mut self, user: Pubkey) -> &mut UserLedger {
+        self.users.entry(user).or_default()
+    }
+
+    fn by_authority(&mut self, authority: &Pubkey) -> &mut UserLedger {
+        self.entry(user_address(authority))
+    }
+
+    pub fn apply(&mut self, event: &StackingEvent) {
+        match event {
+            StackingEvent::BuyFctrTokens(e) => {
+                let ledger = self.by_authority(&e.header.user);
+                ledger.lamports_spent += e.lamports;
+                ledger.fctr_bought += e.amount;
+            }
+            StackingEvent::SellFctrTokens(e) => {
+                let ledger = self.by_authority(&e.header.user);
+                ledger.lamports_received += e.lamports;
+                ledger.fctr_sold += e.amount;
+            }
+            StackingEvent::SellBcdevTokens(e) => {
+                let ledger = self.by_authority(&e.header.user);
+                ledger.lamports_received += e.lamports;
+                ledger.bcdev_sold += e.amount;
+            }
+            StackingEvent::Stake(e) => self.by_authority(&e.owner).staked += e.amount,
+            StackingEvent::Unstake(e) => {
+                let ledger = self.by_authority(&e.owner);
+                ledger.unstaked += e.principal_returned;
+                ledger.rewards += e.staker_reward;
+                for grantor_return in &e.grantor_returns {
+                    let ledger = self.entry(grantor_return.grantor);
+                    ledger.grants_returned += grantor_return.amount;
+                    ledger.rewards += grantor_return.reward;
+                }
+            }
+            StackingEvent::Grant(e) => {
+                self.entry(e.from).granted += e.amount;
+                self.entry(e.to).grants_received += e.amount;
+            }
+            StackingEvent::RevokeGrant(e) => self.entry(e.from).grants_returned += e.amount,
+            StackingEvent::ReclaimGrant(e) => {
+                let ledger = self.entry(e.from);
+                ledger.grants_returned += e.amount;
+                ledger.rewards += e.reward;
+            }
+            StackingEvent::Claim(e) => self.entry(e.from).claimed += e.amount,
+            StackingEvent::PositionOpened(e) => self.by_authority(&e.owner).staked += e.amount,
+            StackingEvent::PositionClosed(e) => {
+                let ledger = self.by_authority(&e.owner);
+                ledger.unstaked += e.amount;
+                ledger.rewards += e.reward;
+            }
+            _ => {}
+        }
+    }
+}
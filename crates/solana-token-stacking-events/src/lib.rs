@@ -0,0 +1,10 @@
+//! Offline tooling for the events emitted by the `solana-token-stacking` program.
+//!
+//! [`parse_logs`] turns captured transaction log lines into typed [`StackingEvent`]s and
+//! [`Ledgers`] folds them into per-user totals.
+
+pub mod decode;
+pub mod ledger;
+
+pub use decode::*;
+pub use ledger::*;
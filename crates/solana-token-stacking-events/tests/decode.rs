@@ -0,0 +1,249 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use solana_token_stacking::events::*;
+use solana_token_stacking_events::{
+    parse_log_line, parse_logs, user_address, DecodeError, Ledgers, StackingEvent,
+};
+
+fn header(user: Pubkey) -> EventHeader {
+    EventHeader {
+        version: EVENT_SCHEMA_VERSION,
+        user,
+        round_index: 1,
+        timestamp: 1_650_000_000,
+    }
+}
+
+fn log_line(event: &impl Event) -> String {
+    format!("Program data: {}", base64::encode(event.data()))
+}
+
+/// Wraps the lines in the invocation of `program`.
+fn invocation(program: &Pubkey, lines: Vec<String>) -> Vec<String> {
+    let mut logs = vec![format!("Program {} invoke [1]", program)];
+    logs.extend(lines);
+    logs.push(format!("Program {} success", program));
+    logs
+}
+
+fn captured_logs(staker: Pubkey, grantor: Pubkey) -> Vec<String> {
+    vec![
+        "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS invoke [1]".to_string(),
+        "Program log: Instruction: BuyTokens".to_string(),
+        log_line(&BuyFctrTokensEvent {
+            header: header(grantor),
+            lamports: 1_000,
+            amount: 109_000,
+            fctr_balance: 109_000,
+            fctr_total_amount: 109_000,
+        }),
+        log_line(&GrantEvent {
+            header: header(grantor),
+            from: user_address(&grantor),
+            to: user_address(&staker),
+            amount: 50_000,
+            grantor_share_bps: 5_000,
+            staked: false,
+            grantor_fctr_balance: 59_000,
+            confidant_amount_deposited: 0,
+        }),
+        log_line(&StakeEvent {
+            header: header(staker),
+            owner: staker,
+            amount: 70_000,
+            granted_amount: 50_000,
+            amount_deposited: 120_000,
+            round_ends: 1_650_000_100,
+        }),
+        "Program log: Stake: 0. Start: 0. End: 0. Now: 0".to_string(),
+        log_line(&UnstakeEvent {
+            header: header(staker),
+            owner: staker,
+            principal_returned: 70_000,
+            total_reward: 3_000,
+            staker_reward: 2_250,
+            grantor_returns: vec![GrantorReturn {
+                grantor: user_address(&grantor),
+                amount: 50_000,
+                reward: 750,
+            }],
+            fctr_balance: 70_000,
+            bcdev_balance: 2_250,
+            on_time: true,
+        }),
+        "Program Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS success".to_string(),
+    ]
+}
+
+#[test]
+fn decodes_events_from_logs() {
+    let staker = Pubkey::new_unique();
+    let grantor = Pubkey::new_unique();
+    let logs = captured_logs(staker, grantor);
+
+    let events = parse_logs(logs.iter().map(String::as_str)).unwrap();
+    assert_eq!(events.len(), 4);
+    assert!(matches!(&events[0], StackingEvent::BuyFctrTokens(e) if e.amount == 109_000));
+    assert!(matches!(&events[1], StackingEvent::Grant(e) if e.grantor_share_bps == 5_000));
+    assert!(matches!(&events[2], StackingEvent::Stake(e) if e.owner == staker));
+    match &events[3] {
+        StackingEvent::Unstake(e) => {
+            assert_eq!(e.header.user, staker);
+            assert_eq!(e.grantor_returns.len(), 1);
+            assert_eq!(e.grantor_returns[0].reward, 750);
+        }
+        _ => panic!("expected an unstake event"),
+    }
+}
+
+#[test]
+fn skips_foreign_lines_and_rejects_garbage() {
+    assert!(parse_log_line("Program log: hello").unwrap().is_none());
+    assert!(parse_log_line("Program data: AAAA").unwrap().is_none());
+    assert!(matches!(
+        parse_log_line("Program data: not base64!"),
+        Err(DecodeError::Base64(_))
+    ));
+
+    let mut event = WithdrawEvent {
+        header: header(Pubkey::new_unique()),
+        lamports: 1,
+    };
+    event.header.version = EVENT_SCHEMA_VERSION + 1;
+    assert!(matches!(
+        parse_log_line(&log_line(&event)),
+        Err(DecodeError::UnsupportedVersion(_))
+    ));
+}
+
+#[test]
+fn decodes_audit_reports() {
+    let auditor = Pubkey::new_unique();
+    let line = log_line(&AuditReportEvent {
+        header: header(auditor),
+        fctr_supply: 109_000,
+        fctr_token_total_amount: 109_000,
+        bcdev_supply: 0,
+        bcdev_token_total_amount: 0,
+        fctr_vault_amount: 1,
+        expected_fctr_vault_amount: 0,
+        sol_vault_lamports: 1_000,
+        sol_liability: 1_000,
+        is_consistent: false,
+        is_paused: true,
+    });
+
+    match parse_log_line(&line).unwrap() {
+        Some(StackingEvent::AuditReport(e)) => {
+            assert_eq!(e.header.user, auditor);
+            assert_eq!(e.fctr_vault_amount, 1);
+            assert!(!e.is_consistent);
+            assert!(e.is_paused);
+        }
+        _ => panic!("expected an audit report"),
+    }
+}
+
+#[test]
+fn decodes_pause_changes() {
+    let authority = Pubkey::new_unique();
+    let line = log_line(&PausedEvent {
+        header: header(authority),
+        is_paused: false,
+    });
+
+    assert!(matches!(
+        parse_log_line(&line).unwrap(),
+        Some(StackingEvent::Paused(e)) if e.header.user == authority && !e.is_paused
+    ));
+}
+
+#[test]
+fn decodes_migration_events() {
+    let authority = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let logs = invocation(
+        &solana_token_stacking::ID,
+        vec![
+            log_line(&PlatformMigratedEvent {
+                header: header(authority),
+                total_sol_in: 5_000,
+            }),
+            log_line(&UserMigratedEvent {
+                header: header(authority),
+                owner,
+                receipt_migrated: true,
+                history_records: 2,
+            }),
+        ],
+    );
+
+    let events = parse_logs(logs.iter().map(String::as_str)).unwrap();
+    assert!(matches!(&events[0], StackingEvent::PlatformMigrated(e) if e.total_sol_in == 5_000));
+    assert!(matches!(
+        &events[1],
+        StackingEvent::UserMigrated(e) if e.owner == owner && e.history_records == 2
+    ));
+}
+
+#[test]
+fn skips_the_data_of_other_programs() {
+    let staker = Pubkey::new_unique();
+    let other_program = Pubkey::new_unique();
+    let stake = StakeEvent {
+        header: header(staker),
+        owner: staker,
+        amount: 70_000,
+        granted_amount: 0,
+        amount_deposited: 70_000,
+        round_ends: 1_650_000_100,
+    };
+    // Another Anchor program with an event of the same name, and one logging several fields.
+    let mut logs = invocation(
+        &other_program,
+        vec![
+            log_line(&stake),
+            "Program data: AAAA not-base64!".to_string(),
+        ],
+    );
+    let mut own_logs = invocation(
+        &solana_token_stacking::ID,
+        vec![
+            format!("Program {} invoke [2]", other_program),
+            log_line(&stake),
+            format!(
+                "Program {} failed: custom program error: 0x1",
+                other_program
+            ),
+            log_line(&stake),
+        ],
+    );
+    logs.append(&mut own_logs);
+    logs.push("Program data: not base64!".to_string());
+
+    let events = parse_logs(logs.iter().map(String::as_str)).unwrap();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(&events[0], StackingEvent::Stake(e) if e.owner == staker));
+}
+
+#[test]
+fn builds_ledgers() {
+    let staker = Pubkey::new_unique();
+    let grantor = Pubkey::new_unique();
+    let logs = captured_logs(staker, grantor);
+    let events = parse_logs(logs.iter().map(String::as_str)).unwrap();
+    let ledgers = Ledgers::from_events(&events);
+
+    let staker_ledger = ledgers.get(&user_address(&staker)).unwrap();
+    assert_eq!(staker_ledger.staked, 70_000);
+    assert_eq!(staker_ledger.unstaked, 70_000);
+    assert_eq!(staker_ledger.grants_received, 50_000);
+    assert_eq!(staker_ledger.rewards, 2_250);
+
+    let grantor_ledger = ledgers.get(&user_address(&grantor)).unwrap();
+    assert_eq!(grantor_ledger.fctr_bought, 109_000);
+    assert_eq!(grantor_ledger.lamports_spent, 1_000);
+    assert_eq!(grantor_ledger.granted, 50_000);
+    assert_eq!(grantor_ledger.grants_returned, 50_000);
+    assert_eq!(grantor_ledger.rewards, 750);
+}
@@ -0,0 +1,7 @@
+//! Offline economic simulation of the token stacking platform.
+//!
+//! A synthetic population goes through the program's own pricing and reward rules, so parameter
+//! changes can be evaluated before they are deployed.
+
+pub mod report;
+pub mod simulation;
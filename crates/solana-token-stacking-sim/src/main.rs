@@ -0,0 +1,121 @@
+use anyhow::Result;
+use clap::{ArgEnum, Parser};
+use solana_token_stacking_sim::simulation::{self, Behavior, Config, Parameters};
+
+#[derive(Parser)]
+#[clap(
+    name = "token-stacking-sim",
+    about = "Simulate the token stacking economy and report it as CSV"
+)]
+struct Opts {
+    /// Report to print.
+    #[clap(arg_enum, default_value = "rounds")]
+    report: ReportKind,
+    #[clap(long, default_value_t = Config::default().users)]
+    users: usize,
+    #[clap(long, default_value_t = Config::default().rounds)]
+    rounds: u64,
+    /// Users register evenly over this many rounds.
+    #[clap(long, default_value_t = Config::default().join_rounds)]
+    join_rounds: u64,
+    #[clap(long, default_value_t = Config::default().seed)]
+    seed: u64,
+
+    /// Round duration in seconds.
+    #[clap(long, default_value_t = Parameters::default().round_duration)]
+    round_duration: u64,
+    /// Registration price in lamports.
+    #[clap(long, default_value_t = Parameters::default().registration_price)]
+    registration_price: u64,
+    /// Lamports in the SOL vault before the first round.
+    #[clap(long, default_value_t = Parameters::default().initial_liquidity)]
+    initial_liquidity: u64,
+    #[clap(long, default_value_t = Parameters::default().max_active_grantors)]
+    max_active_grantors: u32,
+    #[clap(long, default_value_t = Parameters::default().min_grant_ratio)]
+    min_grant_ratio: f64,
+    #[clap(long, default_value_t = Parameters::default().max_grant_ratio)]
+    max_grant_ratio: f64,
+    #[clap(long, default_value_t = Parameters::default().min_grantor_share_bps)]
+    min_grantor_share_bps: u16,
+    #[clap(long, default_value_t = Parameters::default().max_grantor_share_bps)]
+    max_grantor_share_bps: u16,
+
+    /// Chance that a user joins the grant program.
+    #[clap(long, default_value_t = Behavior::default().grant_program)]
+    grant_program_chance: f64,
+    /// Chance that a user buys FCTR in a round.
+    #[clap(long, default_value_t = Behavior::default().buy)]
+    buy_chance: f64,
+    /// Smallest buy in lamports.
+    #[clap(long, default_value_t = Behavior::default().min_buy)]
+    min_buy: u64,
+    /// Largest buy in lamports.
+    #[clap(long, default_value_t = Behavior::default().max_buy)]
+    max_buy: u64,
+    /// Chance that a user stakes in a round.
+    #[clap(long, default_value_t = Behavior::default().stake)]
+    stake_chance: f64,
+    /// Chance that a user who doesn't stake grants to a staker.
+    #[clap(long, default_value_t = Behavior::default().grant)]
+    grant_chance: f64,
+    /// Smallest part of the grantor's FCTR that is granted.
+    #[clap(long, default_value_t = Behavior::default().min_grant_fraction)]
+    min_grant_fraction: f64,
+    /// Largest part of the grantor's FCTR that is granted.
+    #[clap(long, default_value_t = Behavior::default().max_grant_fraction)]
+    max_grant_fraction: f64,
+    /// Chance that a user sells their BCDEV after a round.
+    #[clap(long, default_value_t = Behavior::default().sell_bcdev)]
+    sell_bcdev_chance: f64,
+    /// Chance that a user sells everything and leaves after a round.
+    #[clap(long, default_value_t = Behavior::default().exit)]
+    exit_chance: f64,
+}
+
+#[derive(ArgEnum, Clone, Copy)]
+enum ReportKind {
+    /// Platform metrics at the end of each round.
+    Rounds,
+    /// Returns of the users grouped by the round they registered in.
+    Cohorts,
+}
+
+fn main() -> Result<()> {
+    let opts = Opts::parse();
+    let config = Config {
+        users: opts.users,
+        rounds: opts.rounds,
+        join_rounds: opts.join_rounds,
+        seed: opts.seed,
+        parameters: Parameters {
+            round_duration: opts.round_duration,
+            registration_price: opts.registration_price,
+            initial_liquidity: opts.initial_liquidity,
+            max_active_grantors: opts.max_active_grantors,
+            min_grant_ratio: opts.min_grant_ratio,
+            max_grant_ratio: opts.max_grant_ratio,
+            min_grantor_share_bps: opts.min_grantor_share_bps,
+            max_grantor_share_bps: opts.max_grantor_share_bps,
+        },
+        behavior: Behavior {
+            grant_program: opts.grant_program_chance,
+            buy: opts.buy_chance,
+            min_buy: opts.min_buy,
+            max_buy: opts.max_buy,
+            stake: opts.stake_chance,
+            grant: opts.grant_chance,
+            min_grant_fraction: opts.min_grant_fraction,
+            max_grant_fraction: opts.max_grant_fraction,
+            sell_bcdev: opts.sell_bcdev_chance,
+            exit: opts.exit_chance,
+        },
+    };
+
+    let report = simulation::run(&config)?;
+    match opts.report {
+        ReportKind::Rounds => print!("{}", report.rounds_csv()),
+        ReportKind::Cohorts => print!("{}", report.cohorts_csv()),
+    }
+    Ok(())
+}
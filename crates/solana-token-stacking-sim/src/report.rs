@@ -0,0 +1,106 @@
+//! Per-round platform metrics and per-cohort returns, written out as CSV.
+
+/// What the simulation produced.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub rounds: Vec<RoundMetrics>,
+    pub cohorts: Vec<CohortReturns>,
+}
+
+/// The platform at the end of a round.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoundMetrics {
+    pub round: u64,
+    pub active_users: usize,
+    pub stakers: usize,
+    pub grants: usize,
+    pub fctr_supply: u64,
+    /// FCTR staked during the round, grants included.
+    pub fctr_staked: u64,
+    pub fctr_granted: u64,
+    pub bcdev_minted: u64,
+    pub bcdev_supply: u64,
+    /// BCDEV minted in the round relative to the supply it started with.
+    pub bcdev_inflation: Option<f64>,
+    pub sol_vault: u64,
+    /// Lamports the SOL vault owes if every token were sold back.
+    pub sol_liability: u64,
+    /// SOL vault over SOL liability, below one the vault can't buy every token back.
+    pub solvency: Option<f64>,
+    /// Sales the SOL vault couldn't pay for.
+    pub failed_sells: usize,
+}
+
+/// What the users who registered in the same round got out of the platform.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CohortReturns {
+    /// Round the users registered in.
+    pub cohort: u64,
+    pub users: usize,
+    pub exited: usize,
+    pub sol_paid: u64,
+    pub sol_received: u64,
+    /// Lamports the FCTR and BCDEV still held would sell for.
+    pub holdings: u64,
+    /// Net return on the SOL paid, counting the holdings as sold.
+    pub return_ratio: Option<f64>,
+}
+
+impl Report {
+    pub fn rounds_csv(&self) -> String {
+        csv(
+            "round,active_users,stakers,grants,fctr_supply,fctr_staked,fctr_granted,\
+             bcdev_minted,bcdev_supply,bcdev_inflation,sol_vault,sol_liability,solvency,\
+             failed_sells",
+            self.rounds.iter().map(|r| {
+                format!(
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                    r.round,
+                    r.active_users,
+                    r.stakers,
+                    r.grants,
+                    r.fctr_supply,
+                    r.fctr_staked,
+                    r.fctr_granted,
+                    r.bcdev_minted,
+                    r.bcdev_supply,
+                    ratio(r.bcdev_inflation),
+                    r.sol_vault,
+                    r.sol_liability,
+                    ratio(r.solvency),
+                    r.failed_sells,
+                )
+            }),
+        )
+    }
+
+    pub fn cohorts_csv(&self) -> String {
+        csv(
+            "cohort,users,exited,sol_paid,sol_received,holdings,return_ratio",
+            self.cohorts.iter().map(|c| {
+                format!(
+                    "{},{},{},{},{},{},{}",
+                    c.cohort,
+                    c.users,
+                    c.exited,
+                    c.sol_paid,
+                    c.sol_received,
+                    c.holdings,
+                    ratio(c.return_ratio),
+                )
+            }),
+        )
+    }
+}
+
+fn csv(header: &str, rows: impl Iterator<Item = String>) -> String {
+    std::iter::once(header.to_string())
+        .chain(rows)
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Ratios without a base, like inflation of an empty supply, are left empty.
+fn ratio(value: Option<f64>) -> String {
+    value.map_or_else(String::new, |value| format!("{:.6}", value))
+}
@@ -0,0 +1,541 @@
+//! A synthetic population buying, staking, granting and selling round after round.
+//!
+//! Trades go through `pricing`, rewards through `rewards` and the totals are kept on a real
+//! `Platform`, so the simulation follows whatever the program does. Users act once per round in
+//! a fixed order: they register, buy, stake or grant, get their rewards when the round ends and
+//! then sell.
+use crate::report::{CohortReturns, Report, RoundMetrics};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Result};
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::Uniform;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use solana_token_stacking::pricing;
+use solana_token_stacking::rewards::{self, Grant};
+use solana_token_stacking::state::{
+    Platform, BPS_DENOMINATOR, DEFAULT_GRANT_OFFER_TTL_ROUNDS, DEFAULT_MAX_ACTIVE_GRANTORS,
+    DEFAULT_MAX_GRANTORS_HISTORY, DEFAULT_MAX_GRANTOR_SHARE_BPS, DEFAULT_MAX_GRANT_RATIO,
+    DEFAULT_MIN_GRANTOR_SHARE_BPS, DEFAULT_MIN_GRANT_RATIO,
+};
+
+/// APR a receipt starts with when its user registers.
+const INITIAL_APR: f64 = 0.01;
+
+/// Platform settings under evaluation.
+#[derive(Clone, Debug)]
+pub struct Parameters {
+    pub round_duration: u64,
+    pub registration_price: u64,
+    /// Lamports added to the SOL vault before the first round.
+    pub initial_liquidity: u64,
+    pub max_active_grantors: u32,
+    pub min_grant_ratio: f64,
+    pub max_grant_ratio: f64,
+    pub min_grantor_share_bps: u16,
+    pub max_grantor_share_bps: u16,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Parameters {
+            round_duration: 7 * 24 * 60 * 60,
+            registration_price: 10_000_000,
+            initial_liquidity: 0,
+            max_active_grantors: DEFAULT_MAX_ACTIVE_GRANTORS,
+            min_grant_ratio: DEFAULT_MIN_GRANT_RATIO,
+            max_grant_ratio: DEFAULT_MAX_GRANT_RATIO,
+            min_grantor_share_bps: DEFAULT_MIN_GRANTOR_SHARE_BPS,
+            max_grantor_share_bps: DEFAULT_MAX_GRANTOR_SHARE_BPS,
+        }
+    }
+}
+
+/// Chances that a user does something in a round, and how much of it.
+#[derive(Clone, Debug)]
+pub struct Behavior {
+    /// Chance that a user joins the grant program when registering.
+    pub grant_program: f64,
+    pub buy: f64,
+    /// Lamports spent on a buy, drawn uniformly between the two.
+    pub min_buy: u64,
+    pub max_buy: u64,
+    pub stake: f64,
+    /// Chance that a user who doesn't stake grants to someone who does.
+    pub grant: f64,
+    /// Part of the grantor's FCTR that is granted, drawn uniformly between the two.
+    pub min_grant_fraction: f64,
+    pub max_grant_fraction: f64,
+    pub sell_bcdev: f64,
+    /// Chance that a user sells everything and leaves once a round is over.
+    pub exit: f64,
+}
+
+impl Default for Behavior {
+    fn default() -> Self {
+        Behavior {
+            grant_program: 0.5,
+            buy: 0.5,
+            min_buy: 100_000_000,
+            max_buy: 2_000_000_000,
+            stake: 0.6,
+            grant: 0.3,
+            min_grant_fraction: 0.1,
+            max_grant_fraction: 0.5,
+            sell_bcdev: 0.3,
+            exit: 0.05,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub users: usize,
+    pub rounds: u64,
+    /// Users register evenly over the first `join_rounds` rounds.
+    pub join_rounds: u64,
+    /// The same seed always plays out the same way.
+    pub seed: u64,
+    pub parameters: Parameters,
+    pub behavior: Behavior,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            users: 100,
+            rounds: 52,
+            join_rounds: 1,
+            seed: 0,
+            parameters: Parameters::default(),
+            behavior: Behavior::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn validate(&self) -> Result<()> {
+        let Config {
+            parameters: p,
+            behavior: b,
+            ..
+        } = self;
+        if self.rounds == 0 || !(1..=self.rounds).contains(&self.join_rounds) {
+            bail!("users have to join between the first and the last round");
+        }
+        if p.round_duration == 0 {
+            bail!("rounds have to last at least a second");
+        }
+        if p.max_active_grantors == 0
+            || p.min_grant_ratio <= 0.0
+            || p.min_grant_ratio > p.max_grant_ratio
+        {
+            bail!("invalid grant limits");
+        }
+        if p.min_grantor_share_bps > p.max_grantor_share_bps
+            || p.max_grantor_share_bps > BPS_DENOMINATOR
+        {
+            bail!("invalid grantor share bounds");
+        }
+        let chances = [
+            b.grant_program,
+            b.buy,
+            b.stake,
+            b.grant,
+            b.sell_bcdev,
+            b.exit,
+        ];
+        if !chances.iter().all(|chance| (0.0..=1.0).contains(chance)) {
+            bail!("chances have to be between 0 and 1");
+        }
+        if b.min_buy == 0 || b.min_buy > b.max_buy {
+            bail!("invalid buy amounts");
+        }
+        if pricing::fctr_for_lamports(b.max_buy).is_none() {
+            bail!("buys of {} lamports overflow FCTR", b.max_buy);
+        }
+        // Granting a whole balance would leave the grantor APR without a base.
+        if b.min_grant_fraction <= 0.0
+            || b.min_grant_fraction > b.max_grant_fraction
+            || b.max_grant_fraction >= 1.0
+        {
+            bail!("grant fractions have to be above 0 and below 1");
+        }
+        Ok(())
+    }
+}
+
+pub fn run(config: &Config) -> Result<Report> {
+    config.validate()?;
+    let mut simulation = Simulation::new(config);
+    let rounds = (1..=config.rounds)
+        .map(|round| simulation.play_round(round))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Report {
+        rounds,
+        cohorts: simulation.cohorts(),
+    })
+}
+
+#[derive(Clone, Debug)]
+struct SimUser {
+    cohort: u64,
+    is_registered: bool,
+    has_exited: bool,
+    grant_program: bool,
+    fctr: u64,
+    bcdev: u64,
+    apr: f64,
+    sol_paid: u64,
+    sol_received: u64,
+}
+
+impl SimUser {
+    fn is_active(&self) -> bool {
+        self.is_registered && !self.has_exited
+    }
+}
+
+struct Stake {
+    staker: usize,
+    /// The staker's own FCTR, grants come on top.
+    own_amount: u64,
+    deposited: u64,
+    stake_duration: u64,
+    grants: Vec<(usize, Grant)>,
+}
+
+struct Simulation<'a> {
+    config: &'a Config,
+    rng: StdRng,
+    platform: Platform,
+    sol_vault: u64,
+    users: Vec<SimUser>,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(config: &'a Config) -> Self {
+        let p = &config.parameters;
+        let platform = Platform {
+            bump: 0,
+            bump_fctr_mint: 0,
+            bump_bcdev_mint: 0,
+            bump_sol_vault: 0,
+            bump_fctr_token_vault: 0,
+            round_index: 0,
+            round_start: 0,
+            is_final: false,
+            round_duration: p.round_duration,
+            fctr_token_total_amount: 0,
+            bcdev_token_total_amount: 0,
+            registration_price: p.registration_price,
+            max_active_grantors: p.max_active_grantors,
+            max_grantors_history: DEFAULT_MAX_GRANTORS_HISTORY,
+            min_grant_ratio: p.min_grant_ratio,
+            max_grant_ratio: p.max_grant_ratio,
+            reclaim_grace_period: p.round_duration,
+            grant_offer_ttl_rounds: DEFAULT_GRANT_OFFER_TTL_ROUNDS,
+            min_grantor_share_bps: p.min_grantor_share_bps,
+            max_grantor_share_bps: p.max_grantor_share_bps,
+            total_fctr_staked: 0,
+            active_stakers: 0,
+            total_fctr_granted: 0,
+            total_bcdev_rewards: 0,
+            total_sol_in: p.initial_liquidity,
+            total_sol_out: 0,
+            total_fctr_pending: 0,
+            total_fctr_offered: 0,
+            is_paused: false,
+            authority: Pubkey::default(),
+        };
+        let users = (0..config.users)
+            .map(|i| SimUser {
+                cohort: 1 + i as u64 * config.join_rounds / config.users as u64,
+                is_registered: false,
+                has_exited: false,
+                grant_program: false,
+                fctr: 0,
+                bcdev: 0,
+                apr: INITIAL_APR,
+                sol_paid: 0,
+                sol_received: 0,
+            })
+            .collect();
+        Simulation {
+            config,
+            rng: StdRng::seed_from_u64(config.seed),
+            platform,
+            sol_vault: p.initial_liquidity,
+            users,
+        }
+    }
+
+    fn play_round(&mut self, round: u64) -> Result<RoundMetrics> {
+        self.platform.round_index = round;
+        self.platform.round_start = (round - 1) * self.platform.round_duration;
+        let bcdev_supply_before = self.platform.bcdev_token_total_amount;
+
+        self.register(round);
+        self.buy(round)?;
+        let mut stakes = self.stake();
+        let grants = self.grant(&mut stakes);
+        let fctr_staked = self.platform.total_fctr_staked;
+        let fctr_granted = self.platform.total_fctr_granted;
+        let bcdev_minted = self.unstake(&stakes, round)?;
+        let failed_sells = self.sell();
+
+        let sol_liability = pricing::sol_liability(
+            self.platform.fctr_token_total_amount,
+            self.platform.bcdev_token_total_amount,
+        );
+        Ok(RoundMetrics {
+            round,
+            active_users: self.users.iter().filter(|u| u.is_active()).count(),
+            stakers: stakes.len(),
+            grants,
+            fctr_supply: self.platform.fctr_token_total_amount,
+            fctr_staked,
+            fctr_granted,
+            bcdev_minted,
+            bcdev_supply: self.platform.bcdev_token_total_amount,
+            bcdev_inflation: ratio(bcdev_minted, bcdev_supply_before),
+            sol_vault: self.sol_vault,
+            sol_liability,
+            solvency: ratio(self.sol_vault, sol_liability),
+            failed_sells,
+        })
+    }
+
+    fn register(&mut self, round: u64) {
+        let price = self.platform.registration_price;
+        for user in self.users.iter_mut().filter(|u| u.cohort == round) {
+            user.is_registered = true;
+            user.grant_program = self.rng.gen_bool(self.config.behavior.grant_program);
+            user.sol_paid += price;
+            self.sol_vault += price;
+            self.platform.total_sol_in += price;
+        }
+    }
+
+    fn buy(&mut self, round: u64) -> Result<()> {
+        let behavior = &self.config.behavior;
+        for user in self.users.iter_mut().filter(|u| u.is_active()) {
+            if !self.rng.gen_bool(behavior.buy) {
+                continue;
+            }
+            let lamports = between(&mut self.rng, behavior.min_buy, behavior.max_buy);
+            let fctr = pricing::fctr_for_lamports(lamports)
+                .ok_or_else(|| anyhow!("a buy of {} lamports overflows FCTR", lamports))?;
+            self.platform.fctr_token_total_amount = self
+                .platform
+                .fctr_token_total_amount
+                .checked_add(fctr)
+                .ok_or_else(|| anyhow!("the FCTR supply overflows in round {}", round))?;
+            user.fctr += fctr;
+            user.sol_paid += lamports;
+            self.sol_vault += lamports;
+            self.platform.total_sol_in += lamports;
+        }
+        Ok(())
+    }
+
+    /// Stakes start at a random time of the round and last until it ends.
+    fn stake(&mut self) -> Vec<Stake> {
+        let round_duration = self.platform.round_duration;
+        let mut stakes = Vec::new();
+        for (staker, user) in self.users.iter_mut().enumerate() {
+            if !user.is_active() || user.fctr == 0 || !self.rng.gen_bool(self.config.behavior.stake)
+            {
+                continue;
+            }
+            let amount = std::mem::take(&mut user.fctr);
+            self.platform.total_fctr_staked += amount;
+            self.platform.active_stakers += 1;
+            stakes.push(Stake {
+                staker,
+                own_amount: amount,
+                deposited: amount,
+                stake_duration: between(&mut self.rng, 1, round_duration),
+                grants: Vec::new(),
+            });
+        }
+        stakes
+    }
+
+    /// Users in the grant program who didn't stake grant into a random stake, which the
+    /// program only accepts within its grant limits.
+    fn grant(&mut self, stakes: &mut [Stake]) -> usize {
+        let behavior = &self.config.behavior;
+        let confidants = (0..stakes.len())
+            .filter(|&i| self.users[stakes[i].staker].grant_program)
+            .collect::<Vec<_>>();
+        let mut grants = 0;
+        for grantor in 0..self.users.len() {
+            let user = &self.users[grantor];
+            if !user.is_active()
+                || !user.grant_program
+                || user.fctr == 0
+                || stakes.iter().any(|s| s.staker == grantor)
+                || confidants.is_empty()
+                || !self.rng.gen_bool(behavior.grant)
+            {
+                continue;
+            }
+            let stake = &mut stakes[confidants[self.rng.gen_range(0, confidants.len())]];
+            let balance = user.fctr;
+            let amount_ratio = balance as f64 / stake.own_amount as f64;
+            let fraction = between(
+                &mut self.rng,
+                behavior.min_grant_fraction,
+                behavior.max_grant_fraction,
+            );
+            let amount = (balance as f64 * fraction) as u64;
+            let grantor_share_bps = between(
+                &mut self.rng,
+                self.platform.min_grantor_share_bps,
+                self.platform.max_grantor_share_bps,
+            );
+            if amount == 0
+                || stake.grants.len() >= self.platform.max_active_grantors as usize
+                || !(self.platform.min_grant_ratio..=self.platform.max_grant_ratio)
+                    .contains(&amount_ratio)
+                || !self.platform.is_valid_grantor_share(grantor_share_bps)
+            {
+                continue;
+            }
+            // Grants made into an active stake are staked for the rest of the round.
+            let duration = between(&mut self.rng, 1, stake.stake_duration);
+            stake.grants.push((
+                grantor,
+                Grant {
+                    amount,
+                    duration,
+                    grantor_share_bps,
+                },
+            ));
+            stake.deposited += amount;
+            let user = &mut self.users[grantor];
+            user.fctr -= amount;
+            user.apr = rewards::grantor_apr(user.apr, balance, amount);
+            self.platform.total_fctr_staked += amount;
+            self.platform.total_fctr_granted += amount;
+            grants += 1;
+        }
+        grants
+    }
+
+    /// Returns the BCDEV minted for the stakes once the round is over.
+    fn unstake(&mut self, stakes: &[Stake], round: u64) -> Result<u64> {
+        let overflow = || anyhow!("the BCDEV supply overflows in round {}", round);
+        let mut minted = 0u64;
+        for stake in stakes {
+            let grants = stake.grants.iter().map(|(_, g)| *g).collect::<Vec<_>>();
+            let total_reward = rewards::total_reward(
+                stake.deposited,
+                self.users[stake.staker].apr,
+                stake.stake_duration,
+                &grants,
+                self.platform.round_duration,
+            );
+            let split = rewards::split_reward(total_reward, &grants);
+            minted = minted.checked_add(split.total()).ok_or_else(overflow)?;
+
+            let staker = &mut self.users[stake.staker];
+            staker.fctr += stake.own_amount;
+            staker.bcdev += split.staker_reward;
+            for ((grantor, grant), reward) in stake.grants.iter().zip(split.grantor_rewards) {
+                let grantor = &mut self.users[*grantor];
+                grantor.fctr += grant.amount;
+                grantor.bcdev += reward;
+                self.platform.total_fctr_granted -= grant.amount;
+            }
+            self.platform.total_fctr_staked -= stake.deposited;
+            self.platform.active_stakers -= 1;
+        }
+        self.platform.bcdev_token_total_amount = self
+            .platform
+            .bcdev_token_total_amount
+            .checked_add(minted)
+            .ok_or_else(overflow)?;
+        self.platform.total_bcdev_rewards = self
+            .platform
+            .total_bcdev_rewards
+            .checked_add(minted)
+            .ok_or_else(overflow)?;
+        Ok(minted)
+    }
+
+    /// Returns the number of sales the SOL vault couldn't pay for, which leave the tokens
+    /// with their holders.
+    fn sell(&mut self) -> usize {
+        let behavior = &self.config.behavior;
+        let mut failed_sells = 0;
+        for user in self.users.iter_mut().filter(|u| u.is_active()) {
+            let exits = self.rng.gen_bool(behavior.exit);
+            if user.bcdev > 0 && (exits || self.rng.gen_bool(behavior.sell_bcdev)) {
+                let lamports = pricing::lamports_for_bcdev(user.bcdev);
+                if lamports <= self.sol_vault {
+                    self.platform.bcdev_token_total_amount -= user.bcdev;
+                    user.bcdev = 0;
+                    pay_out(user, &mut self.platform, &mut self.sol_vault, lamports);
+                } else {
+                    failed_sells += 1;
+                }
+            }
+            if exits {
+                let lamports = pricing::lamports_for_fctr(user.fctr);
+                if lamports <= self.sol_vault {
+                    self.platform.fctr_token_total_amount -= user.fctr;
+                    user.fctr = 0;
+                    pay_out(user, &mut self.platform, &mut self.sol_vault, lamports);
+                    user.has_exited = true;
+                } else {
+                    failed_sells += 1;
+                }
+            }
+        }
+        failed_sells
+    }
+
+    fn cohorts(&self) -> Vec<CohortReturns> {
+        (1..=self.config.join_rounds)
+            .map(|cohort| {
+                let users = self.users.iter().filter(|u| u.cohort == cohort);
+                let returns = users.fold(
+                    CohortReturns {
+                        cohort,
+                        ..CohortReturns::default()
+                    },
+                    |mut returns, user| {
+                        returns.users += 1;
+                        returns.exited += user.has_exited as usize;
+                        returns.sol_paid += user.sol_paid;
+                        returns.sol_received += user.sol_received;
+                        returns.holdings += pricing::sol_liability(user.fctr, user.bcdev);
+                        returns
+                    },
+                );
+                CohortReturns {
+                    return_ratio: ratio(returns.sol_received + returns.holdings, returns.sol_paid)
+                        .map(|ratio| ratio - 1.0),
+                    ..returns
+                }
+            })
+            .collect()
+    }
+}
+
+fn pay_out(user: &mut SimUser, platform: &mut Platform, sol_vault: &mut u64, lamports: u64) {
+    *sol_vault -= lamports;
+    platform.total_sol_out += lamports;
+    user.sol_received += lamports;
+}
+
+/// Draws uniformly from `low..=high`.
+fn between<T: SampleUniform>(rng: &mut StdRng, low: T, high: T) -> T {
+    rng.sample(Uniform::new_inclusive(low, high))
+}
+
+fn ratio(value: u64, base: u64) -> Option<f64> {
+    (base > 0).then(|| value as f64 / base as f64)
+}
@@ -0,0 +1,106 @@
+use solana_token_stacking::pricing;
+use solana_token_stacking_sim::simulation::{self, Behavior, Config};
+
+fn config() -> Config {
+    Config {
+        users: 40,
+        rounds: 12,
+        join_rounds: 4,
+        seed: 7,
+        ..Config::default()
+    }
+}
+
+#[test]
+fn plays_out_the_same_for_the_same_seed() {
+    let first = simulation::run(&config()).unwrap();
+    let second = simulation::run(&config()).unwrap();
+    assert_eq!(first.rounds_csv(), second.rounds_csv());
+    assert_eq!(first.cohorts_csv(), second.cohorts_csv());
+
+    let other = simulation::run(&Config {
+        seed: 8,
+        ..config()
+    })
+    .unwrap();
+    assert_ne!(first.rounds_csv(), other.rounds_csv());
+}
+
+#[test]
+fn mints_bcdev_to_stakers() {
+    let report = simulation::run(&config()).unwrap();
+
+    assert_eq!(report.rounds.len(), 12);
+    let minted = report.rounds.iter().map(|r| r.bcdev_minted).sum::<u64>();
+    assert!(minted > 0);
+    assert!(report.rounds.iter().any(|r| r.grants > 0));
+    let last = report.rounds.last().unwrap();
+    assert!(last.bcdev_supply <= minted);
+    assert_eq!(
+        last.sol_liability,
+        pricing::sol_liability(last.fctr_supply, last.bcdev_supply)
+    );
+}
+
+#[test]
+fn nothing_is_minted_without_stakers() {
+    let report = simulation::run(&Config {
+        behavior: Behavior {
+            stake: 0.0,
+            exit: 0.0,
+            ..Behavior::default()
+        },
+        ..config()
+    })
+    .unwrap();
+
+    assert!(report.rounds.iter().all(|r| r.bcdev_minted == 0));
+    assert!(report.rounds.iter().all(|r| r.bcdev_inflation.is_none()));
+    // FCTR sells back above its buying price, so holding it alone makes a profit.
+    assert!(report.cohorts.iter().all(|c| c.return_ratio.unwrap() > 0.0));
+}
+
+#[test]
+fn groups_users_by_the_round_they_joined() {
+    let report = simulation::run(&config()).unwrap();
+
+    assert_eq!(report.cohorts.len(), 4);
+    assert_eq!(report.cohorts.iter().map(|c| c.users).sum::<usize>(), 40);
+    assert!(report.cohorts.iter().all(|c| c.sol_paid > 0));
+    let csv = report.cohorts_csv();
+    assert_eq!(csv.lines().count(), 5);
+    assert!(csv.starts_with("cohort,users,exited,"));
+}
+
+#[test]
+fn rejects_invalid_configs() {
+    for config in [
+        Config {
+            join_rounds: 13,
+            ..config()
+        },
+        Config {
+            behavior: Behavior {
+                buy: 1.5,
+                ..Behavior::default()
+            },
+            ..config()
+        },
+        Config {
+            behavior: Behavior {
+                max_grant_fraction: 1.0,
+                ..Behavior::default()
+            },
+            ..config()
+        },
+        Config {
+            behavior: Behavior {
+                max_buy: u64::MAX,
+                ..Behavior::default()
+            },
+            ..config()
+        },
+    ] {
+        assert!(simulation::run(&config).is_err());
+    }
+}
@@ -34,4 +34,36 @@ pub enum CustomErrors {
     GrantProgramError,
     #[msg("Invalid grantors list")]
     InvalidGrantorsList,
+    #[msg("Invalid lock tier")]
+    InvalidLockTier,
+    #[msg("Invalid position amount")]
+    InvalidPositionAmount,
+    #[msg("Position is still locked")]
+    PositionLocked,
+    #[msg("Signer is neither the user authority nor the operator")]
+    UnauthorizedSigner,
+    #[msg("User is not staked in the current round")]
+    NotStakedInRound,
+    #[msg("Invalid grant limits")]
+    InvalidGrantLimits,
+    #[msg("Receipt must be resized to the current grant limits")]
+    ReceiptNotResized,
+    #[msg("Receipt already fits the current grant limits")]
+    ReceiptAlreadySized,
+    #[msg("Grant can't be reclaimed before the grace period ends")]
+    ReclaimTooEarly,
+    #[msg("Grant offer expired")]
+    GrantOfferExpired,
+    #[msg("Grantor share is out of the platform bounds")]
+    InvalidGrantorShare,
+    #[msg("Too many trust list entries")]
+    TrustListFull,
+    #[msg("Grant is not permitted by the trust lists")]
+    TrustListViolation,
+    #[msg("Platform is paused")]
+    PlatformPaused,
+    #[msg("Account is already migrated")]
+    AccountAlreadyMigrated,
+    #[msg("Amount to buy is too large")]
+    BuyAmountTooLarge,
 }
@@ -1,59 +1,296 @@
+use crate::state::TrustListMode;
 use anchor_lang::prelude::*;
 
+/// Layout version of every event, bumped whenever an event's fields change.
+pub const EVENT_SCHEMA_VERSION: u8 = 1;
+
+/// Common part of every event: who acted, in which round and when.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug)]
+pub struct EventHeader {
+    pub version: u8,
+    pub user: Pubkey,
+    pub round_index: u64,
+    pub timestamp: i64,
+}
+
+impl EventHeader {
+    pub fn new(user: Pubkey, round_index: u64) -> Result<Self> {
+        Ok(EventHeader {
+            version: EVENT_SCHEMA_VERSION,
+            user,
+            round_index,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
+
 #[event]
-pub struct PlatformInitializeEvent {}
+pub struct PlatformInitializeEvent {
+    pub header: EventHeader,
+    pub round_duration: u64,
+    pub registration_price: u64,
+}
 
 #[event]
 pub struct UserRegisteredEvent {
-    pub user: Pubkey,
+    pub header: EventHeader,
+    pub grant_program: bool,
+    pub registration_price: u64,
 }
 
 #[event]
-pub struct WithdrawEvent {}
+pub struct OperatorUpdatedEvent {
+    pub header: EventHeader,
+    pub operator: Option<Pubkey>,
+}
+
+#[event]
+pub struct TrustListUpdatedEvent {
+    pub header: EventHeader,
+    pub mode: TrustListMode,
+    pub entries_count: u32,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub header: EventHeader,
+    pub lamports: u64,
+}
 
 #[event]
 pub struct BuyFctrTokensEvent {
+    pub header: EventHeader,
+    pub lamports: u64,
     pub amount: u64,
+    pub fctr_balance: u64,
+    pub fctr_total_amount: u64,
 }
 
 #[event]
 pub struct SellFctrTokensEvent {
+    pub header: EventHeader,
     pub amount: u64,
+    pub lamports: u64,
+    pub fctr_total_amount: u64,
 }
 
 #[event]
 pub struct SellBcdevTokensEvent {
+    pub header: EventHeader,
     pub amount: u64,
+    pub lamports: u64,
+    pub bcdev_balance: u64,
+    pub bcdev_total_amount: u64,
 }
 
 #[event]
 pub struct RoundStartEvent {
+    pub header: EventHeader,
     pub is_final: bool,
+    pub round_start: u64,
+    pub round_ends: u64,
+}
+
+#[event]
+pub struct GrantLimitsUpdatedEvent {
+    pub header: EventHeader,
+    pub max_active_grantors: u32,
+    pub max_grantors_history: u32,
+    pub min_grant_ratio: f64,
+    pub max_grant_ratio: f64,
+}
+
+#[event]
+pub struct ReclaimGracePeriodUpdatedEvent {
+    pub header: EventHeader,
+    pub grace_period: u64,
+}
+
+#[event]
+pub struct GrantOfferTtlUpdatedEvent {
+    pub header: EventHeader,
+    pub ttl_rounds: u64,
+}
+
+#[event]
+pub struct GrantorShareBoundsUpdatedEvent {
+    pub header: EventHeader,
+    pub min_grantor_share_bps: u16,
+    pub max_grantor_share_bps: u16,
+}
+
+#[event]
+pub struct AuditReportEvent {
+    pub header: EventHeader,
+    pub fctr_supply: u64,
+    pub fctr_token_total_amount: u64,
+    pub bcdev_supply: u64,
+    pub bcdev_token_total_amount: u64,
+    pub fctr_vault_amount: u64,
+    pub expected_fctr_vault_amount: u64,
+    pub sol_vault_lamports: u64,
+    pub sol_liability: u64,
+    pub is_consistent: bool,
+    pub is_paused: bool,
+}
+
+#[event]
+pub struct PausedEvent {
+    pub header: EventHeader,
+    pub is_paused: bool,
+}
+
+#[event]
+pub struct ReceiptResizedEvent {
+    pub header: EventHeader,
+    pub owner: Pubkey,
+    pub size: u64,
+    pub grant_history_capacity: u32,
+}
+
+#[event]
+pub struct PlatformMigratedEvent {
+    pub header: EventHeader,
+    pub total_sol_in: u64,
+}
+
+#[event]
+pub struct UserMigratedEvent {
+    pub header: EventHeader,
+    pub owner: Pubkey,
+    pub receipt_migrated: bool,
+    pub history_records: u32,
 }
 
 #[event]
 pub struct LiquidityAddedEvent {
-    pub amount: u64,
+    pub header: EventHeader,
+    pub lamports: u64,
 }
 
 #[event]
 pub struct StakeEvent {
+    pub header: EventHeader,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub granted_amount: u64,
+    pub amount_deposited: u64,
+    pub round_ends: u64,
+}
+
+/// What a single grantor got back when his confidant unstaked.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug)]
+pub struct GrantorReturn {
+    pub grantor: Pubkey,
     pub amount: u64,
+    pub reward: u64,
+}
+
+impl GrantorReturn {
+    pub const SPACE: usize = 32 + 8 + 8;
 }
 
 #[event]
-pub struct UnstakeEvent {}
+pub struct UnstakeEvent {
+    pub header: EventHeader,
+    pub owner: Pubkey,
+    pub principal_returned: u64,
+    pub total_reward: u64,
+    pub staker_reward: u64,
+    pub grantor_returns: Vec<GrantorReturn>,
+    pub fctr_balance: u64,
+    pub bcdev_balance: u64,
+    pub on_time: bool,
+}
 
 #[event]
 pub struct GrantEvent {
+    pub header: EventHeader,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub grantor_share_bps: u16,
+    pub staked: bool,
+    pub grantor_fctr_balance: u64,
+    pub confidant_amount_deposited: u64,
+}
+
+#[event]
+pub struct GrantOfferEvent {
+    pub header: EventHeader,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub grantor_share_bps: u16,
+    pub expires_round: u64,
+    pub grantor_fctr_balance: u64,
+}
+
+#[event]
+pub struct GrantOfferRejectedEvent {
+    pub header: EventHeader,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub expired: bool,
+    pub grantor_fctr_balance: u64,
+}
+
+#[event]
+pub struct RevokeGrantEvent {
+    pub header: EventHeader,
     pub from: Pubkey,
     pub to: Pubkey,
     pub amount: u64,
+    pub grantor_fctr_balance: u64,
+}
+
+#[event]
+pub struct ReclaimGrantEvent {
+    pub header: EventHeader,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub reward: u64,
+    pub grantor_fctr_balance: u64,
+    pub grantor_bcdev_balance: u64,
+    pub confidant_amount_deposited: u64,
 }
 
 #[event]
 pub struct ClaimEvent {
+    pub header: EventHeader,
     pub from: Pubkey,
     pub to: Pubkey,
     pub amount: u64,
+    pub grantor_fctr_balance: u64,
+    pub confidant_amount_deposited: u64,
+}
+
+#[event]
+pub struct PositionOpenedEvent {
+    pub header: EventHeader,
+    pub owner: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+    pub lock_rounds: u8,
+    pub unlock_ts: u64,
+}
+
+#[event]
+pub struct PositionClosedEvent {
+    pub header: EventHeader,
+    pub owner: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+    pub reward: u64,
+    pub fctr_balance: u64,
+    pub bcdev_balance: u64,
+}
+
+#[event]
+pub struct SnapshotEvent {
+    pub header: EventHeader,
+    pub weight: u64,
+    pub total_weight: u64,
 }
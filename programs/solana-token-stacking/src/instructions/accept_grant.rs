@@ -0,0 +1,106 @@
+use crate::instructions::grant_tokens::{
+    check_trust_lists, record_grant, record_grant_stats, GrantConfidant, GrantTerms,
+};
+use crate::state::{GrantHistory, GrantOffer, Receipt, Reputation, TrustList, UserStats};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, GrantEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct AcceptGrant<'info> {
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(mut, seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
+    receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"grant_history", authority.key().as_ref()], bump = grant_history.bump)]
+    grant_history: Box<Account<'info, GrantHistory>>,
+    #[account(mut, seeds = [b"reputation", authority.key().as_ref()], bump = reputation.bump)]
+    reputation: Box<Account<'info, Reputation>>,
+    #[account(address = user.authority)]
+    authority: Signer<'info>,
+    #[account(seeds = [b"user", grantor_authority.key().as_ref()], bump = grantor_user.bump)]
+    grantor_user: Account<'info, User>,
+    #[account(mut, seeds = [b"receipt", grantor_authority.key().as_ref()], bump = grantor_user.bump_receipt)]
+    grantor_receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
+    #[account(mut, seeds = [b"user_stats", grantor_authority.key().as_ref()], bump = grantor_user_stats.bump)]
+    grantor_user_stats: Box<Account<'info, UserStats>>,
+    #[account(seeds = [b"fctr_vault", grantor_authority.key().as_ref()], bump = grantor_user.bump_fctr_vault)]
+    grantor_fctr_vault: Account<'info, TokenAccount>,
+    /// CHECK:
+    #[account(mut)]
+    grantor_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = grantor_authority,
+        seeds = [b"grant_offer", grantor_authority.key().as_ref(), authority.key().as_ref()],
+        bump = grant_offer.bump,
+    )]
+    grant_offer: Account<'info, GrantOffer>,
+    #[account(seeds = [b"trust_list", authority.key().as_ref()], bump = trust_list.bump)]
+    trust_list: Box<Account<'info, TrustList>>,
+    #[account(seeds = [b"trust_list", grantor_authority.key().as_ref()], bump = grantor_trust_list.bump)]
+    grantor_trust_list: Box<Account<'info, TrustList>>,
+    #[account(
+        mut, seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
+    platform: Box<Account<'info, Platform>>,
+}
+
+pub fn accept_grant(ctx: Context<AcceptGrant>) -> Result<()> {
+    if ctx.accounts.grantor_receipt.authority != ctx.accounts.grantor_authority.key() {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    if ctx.accounts.platform.round_index > ctx.accounts.grant_offer.expires_round {
+        return err!(CustomErrors::GrantOfferExpired);
+    }
+    check_trust_lists(&ctx.accounts.grantor_trust_list, &ctx.accounts.trust_list)?;
+    let amount = ctx.accounts.grant_offer.amount;
+    ctx.accounts.platform.total_fctr_offered -= amount;
+    record_grant(
+        &mut ctx.accounts.platform,
+        &ctx.accounts.grantor_user,
+        &mut ctx.accounts.grantor_receipt,
+        ctx.accounts.grantor_fctr_vault.amount + amount,
+        GrantConfidant {
+            user: &ctx.accounts.user,
+            receipt: &mut ctx.accounts.receipt,
+            grant_history: &mut ctx.accounts.grant_history,
+            reputation: &mut ctx.accounts.reputation,
+        },
+        GrantTerms {
+            amount,
+            grantor_share_bps: ctx.accounts.grant_offer.grantor_share_bps,
+        },
+    )?;
+
+    record_grant_stats(
+        &mut ctx.accounts.grantor_user_stats,
+        &mut ctx.accounts.user_stats,
+        amount,
+    );
+
+    let now: u64 = Clock::get()?.unix_timestamp as _;
+    emit!(GrantEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        from: ctx.accounts.grantor_user.key(),
+        to: ctx.accounts.user.key(),
+        amount,
+        grantor_share_bps: ctx.accounts.grant_offer.grantor_share_bps,
+        staked: ctx.accounts.receipt.is_valid && ctx.accounts.receipt.round_ends > now,
+        grantor_fctr_balance: ctx.accounts.grantor_fctr_vault.amount,
+        confidant_amount_deposited: ctx.accounts.receipt.amount_deposited,
+    });
+
+    Ok(())
+}
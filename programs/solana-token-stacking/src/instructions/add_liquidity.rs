@@ -1,4 +1,7 @@
-use crate::{events::LiquidityAddedEvent, state::Platform};
+use crate::{
+    events::{EventHeader, LiquidityAddedEvent},
+    state::Platform,
+};
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke, system_instruction},
@@ -29,7 +32,15 @@ pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
         ],
     )?;
 
-    emit!(LiquidityAddedEvent { amount });
+    ctx.accounts.platform.total_sol_in += amount;
+
+    emit!(LiquidityAddedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        lamports: amount,
+    });
 
     Ok(())
 }
@@ -0,0 +1,68 @@
+use crate::{
+    events::{AuditReportEvent, EventHeader},
+    pricing,
+    state::Platform,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct AuditPlatform<'info> {
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+    #[account(seeds = [b"fctr_mint"], bump = platform.bump_fctr_mint)]
+    fctr_mint: Account<'info, Mint>,
+    #[account(seeds = [b"bcdev_mint"], bump = platform.bump_bcdev_mint)]
+    bcdev_mint: Account<'info, Mint>,
+    #[account(seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
+    platform_fctr_token_vault: Account<'info, TokenAccount>,
+    /// CHECK:
+    #[account(seeds = [b"sol_vault"], bump = platform.bump_sol_vault)]
+    sol_vault: AccountInfo<'info>,
+    auditor: Signer<'info>,
+}
+
+/// Anyone can check the platform totals against the mints and vaults.
+/// With `auto_pause` a mismatch pauses the platform until the owner resumes it. The FCTR
+/// vault may hold more than the totals, since anyone can send tokens to it.
+pub fn audit_platform(ctx: Context<AuditPlatform>, auto_pause: bool) -> Result<()> {
+    let platform = &ctx.accounts.platform;
+    let fctr_supply = ctx.accounts.fctr_mint.supply;
+    let bcdev_supply = ctx.accounts.bcdev_mint.supply;
+    let fctr_vault_amount = ctx.accounts.platform_fctr_token_vault.amount;
+    let expected_fctr_vault_amount =
+        platform.total_fctr_staked + platform.total_fctr_pending + platform.total_fctr_offered;
+    let sol_vault_lamports = ctx
+        .accounts
+        .sol_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    let sol_liability = pricing::sol_liability(fctr_supply, bcdev_supply);
+
+    let is_consistent = fctr_supply == platform.fctr_token_total_amount
+        && bcdev_supply == platform.bcdev_token_total_amount
+        && fctr_vault_amount >= expected_fctr_vault_amount
+        && sol_vault_lamports >= sol_liability;
+    if !is_consistent && auto_pause {
+        ctx.accounts.platform.is_paused = true;
+    }
+
+    emit!(AuditReportEvent {
+        header: EventHeader::new(
+            ctx.accounts.auditor.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        fctr_supply,
+        fctr_token_total_amount: ctx.accounts.platform.fctr_token_total_amount,
+        bcdev_supply,
+        bcdev_token_total_amount: ctx.accounts.platform.bcdev_token_total_amount,
+        fctr_vault_amount,
+        expected_fctr_vault_amount,
+        sol_vault_lamports,
+        sol_liability,
+        is_consistent,
+        is_paused: ctx.accounts.platform.is_paused,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,14 @@
 use crate::{
     errors::CustomErrors,
-    events::BuyFctrTokensEvent,
-    state::{Platform, User},
-    FCTR_DECIMALS,
+    events::{BuyFctrTokensEvent, EventHeader},
+    pricing,
+    state::{Platform, User, UserStats},
 };
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke, system_instruction},
 };
 use anchor_spl::token;
-use anchor_spl::token::spl_token::native_mint::DECIMALS;
 use anchor_spl::token::{Mint, MintTo, Token, TokenAccount};
 
 #[derive(Accounts)]
@@ -18,10 +17,16 @@ pub struct BuyTokens<'info> {
     user: Account<'info, User>,
     #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
     fctr_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
     /// CHECK:
     #[account(mut, seeds = [b"sol_vault"], bump = platform.bump_sol_vault)]
     sol_vault: AccountInfo<'info>,
-    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    #[account(
+        mut, seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
     platform: Account<'info, Platform>,
     #[account(mut, seeds = [b"fctr_mint"], bump = platform.bump_fctr_mint)]
     fctr_mint: Account<'info, Mint>,
@@ -32,7 +37,7 @@ pub struct BuyTokens<'info> {
 }
 
 pub fn buy_tokens(ctx: Context<BuyTokens>, lamports: u64) -> Result<()> {
-    let fctr_count = 109 * lamports * 10u64.pow((FCTR_DECIMALS - DECIMALS) as _);
+    let fctr_count = pricing::fctr_for_lamports(lamports).ok_or(CustomErrors::BuyAmountTooLarge)?;
     if fctr_count < 10 {
         return err!(CustomErrors::InvalidBuyAmount);
     }
@@ -49,6 +54,9 @@ pub fn buy_tokens(ctx: Context<BuyTokens>, lamports: u64) -> Result<()> {
     )?;
     ctx.accounts.user.user_fctr_amount += fctr_count;
     ctx.accounts.platform.fctr_token_total_amount += fctr_count;
+    ctx.accounts.platform.total_sol_in += lamports;
+    ctx.accounts.user_stats.fctr_bought += fctr_count;
+    ctx.accounts.user_stats.sol_paid += lamports;
     let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
     let cpi_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
@@ -61,7 +69,16 @@ pub fn buy_tokens(ctx: Context<BuyTokens>, lamports: u64) -> Result<()> {
     );
     token::mint_to(cpi_ctx, fctr_count)?;
 
-    emit!(BuyFctrTokensEvent { amount: fctr_count });
+    emit!(BuyFctrTokensEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        lamports,
+        amount: fctr_count,
+        fctr_balance: ctx.accounts.fctr_vault.amount + fctr_count,
+        fctr_total_amount: ctx.accounts.platform.fctr_token_total_amount,
+    });
 
     Ok(())
 }
@@ -1,8 +1,8 @@
 use crate::state::Receipt;
 use crate::{
     errors::CustomErrors,
-    events::ClaimEvent,
-    state::{Platform, User},
+    events::{ClaimEvent, EventHeader},
+    state::{Platform, User, UserStats},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token;
@@ -13,12 +13,17 @@ use anchor_spl::token::{Token, TokenAccount, Transfer};
 pub struct ClaimTokens<'info> {
     #[account(mut, seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
     receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
     #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
     user: Account<'info, User>,
     #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
     fctr_vault: Account<'info, TokenAccount>,
-    #[account(mut, address = user.authority)]
-    authority: Signer<'info>,
+    /// CHECK:
+    #[account(address = user.authority)]
+    authority: UncheckedAccount<'info>,
+    #[account(constraint = user.can_operate(&signer.key()) @ CustomErrors::UnauthorizedSigner)]
+    signer: Signer<'info>,
     #[account(seeds = [b"user", confidant_authority.key().as_ref()], bump = confidant_user.bump)]
     confidant_user: Account<'info, User>,
     #[account(mut, seeds = [b"receipt", confidant_authority.key().as_ref()], bump = confidant_user.bump_receipt)]
@@ -53,6 +58,9 @@ pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
         .grantors
         .retain(|g| g.grantor != ctx.accounts.user.key());
     ctx.accounts.confidant_receipt.amount_deposited -= granted_amount;
+    ctx.accounts.platform.total_fctr_staked -= granted_amount;
+    ctx.accounts.platform.total_fctr_granted -= granted_amount;
+    ctx.accounts.user_stats.fctr_claimed += granted_amount;
 
     let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
     let cpi_ctx = CpiContext::new_with_signer(
@@ -67,9 +75,12 @@ pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
     token::transfer(cpi_ctx, granted_amount)?;
 
     emit!(ClaimEvent {
+        header: EventHeader::new(ctx.accounts.signer.key(), ctx.accounts.platform.round_index)?,
         from: ctx.accounts.user.key(),
         to: ctx.accounts.confidant_user.key(),
         amount: granted_amount,
+        grantor_fctr_balance: ctx.accounts.fctr_vault.amount + granted_amount,
+        confidant_amount_deposited: ctx.accounts.confidant_receipt.amount_deposited,
     });
 
     Ok(())
@@ -0,0 +1,102 @@
+use crate::state::Position;
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, PositionClosedEvent},
+    state::{Platform, User, UserStats},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, MintTo, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut, seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"position", authority.key().as_ref(), position.id.to_le_bytes().as_ref()],
+        bump = position.bump,
+    )]
+    position: Account<'info, Position>,
+    #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
+    fctr_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"bcdev_vault", authority.key().as_ref()], bump = user.bump_bcdev_vault)]
+    bcdev_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
+    #[account(mut, address = user.authority)]
+    authority: Signer<'info>,
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
+    platform_fctr_token_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"bcdev_mint"], bump = platform.bump_bcdev_mint)]
+    bcdev_mint: Account<'info, Mint>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    let now: u64 = Clock::get()?.unix_timestamp as _;
+    if ctx.accounts.position.authority != ctx.accounts.authority.key() {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    if now <= ctx.accounts.position.unlock_ts {
+        return err!(CustomErrors::PositionLocked);
+    }
+    let amount = ctx.accounts.position.amount_deposited;
+    let reward =
+        calculate_position_reward(&ctx.accounts.position, ctx.accounts.platform.round_duration);
+
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.platform_fctr_token_vault.to_account_info(),
+            to: ctx.accounts.fctr_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let mint_cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.bcdev_mint.to_account_info(),
+            to: ctx.accounts.bcdev_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::mint_to(mint_cpi_ctx, reward)?;
+
+    ctx.accounts.user.user_fctr_amount += amount;
+    ctx.accounts.platform.bcdev_token_total_amount += reward;
+    ctx.accounts.platform.total_bcdev_rewards += reward;
+    ctx.accounts.platform.total_fctr_staked -= amount;
+    ctx.accounts.user_stats.bcdev_earned += reward;
+
+    emit!(PositionClosedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        owner: ctx.accounts.authority.key(),
+        id: ctx.accounts.position.id,
+        amount,
+        reward,
+        fctr_balance: ctx.accounts.fctr_vault.amount + amount,
+        bcdev_balance: ctx.accounts.bcdev_vault.amount + reward,
+    });
+
+    Ok(())
+}
+
+/// Locked rounds are rewarded as full rounds on top of the time staked in the opening round.
+fn calculate_position_reward(position: &Position, round_duration: u64) -> u64 {
+    let locked_duration = position.stake_duration + position.lock_rounds as u64 * round_duration;
+    (position.amount_deposited as f64 * position.apr * locked_duration as f64
+        / round_duration as f64) as u64
+}
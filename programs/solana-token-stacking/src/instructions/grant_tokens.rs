@@ -1,7 +1,10 @@
-use crate::state::{GrantorHistoryRecord, GrantorRecord, Receipt};
+use crate::state::{
+    GrantHistory, GrantorHistoryRecord, GrantorRecord, Receipt, Reputation, TrustList, UserStats,
+};
 use crate::{
     errors::CustomErrors,
-    events::GrantEvent,
+    events::{EventHeader, GrantEvent},
+    rewards,
     state::{Platform, User},
 };
 use anchor_lang::prelude::*;
@@ -19,13 +22,29 @@ pub struct GrantTokens<'info> {
     fctr_vault: Account<'info, TokenAccount>,
     #[account(mut, address = user.authority)]
     authority: Signer<'info>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
+    #[account(mut, seeds = [b"user_stats", confidant_authority.key().as_ref()], bump = confidant_user_stats.bump)]
+    confidant_user_stats: Box<Account<'info, UserStats>>,
     #[account(seeds = [b"user", confidant_authority.key().as_ref()], bump = confidant_user.bump)]
     confidant_user: Account<'info, User>,
     #[account(mut, seeds = [b"receipt", confidant_authority.key().as_ref()], bump = confidant_user.bump_receipt)]
     confidant_receipt: Account<'info, Receipt>,
     /// CHECK:
     confidant_authority: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    #[account(mut, seeds = [b"grant_history", confidant_authority.key().as_ref()], bump = confidant_grant_history.bump)]
+    confidant_grant_history: Box<Account<'info, GrantHistory>>,
+    #[account(mut, seeds = [b"reputation", confidant_authority.key().as_ref()], bump = confidant_reputation.bump)]
+    confidant_reputation: Box<Account<'info, Reputation>>,
+    #[account(seeds = [b"trust_list", authority.key().as_ref()], bump = trust_list.bump)]
+    trust_list: Box<Account<'info, TrustList>>,
+    #[account(seeds = [b"trust_list", confidant_authority.key().as_ref()], bump = confidant_trust_list.bump)]
+    confidant_trust_list: Box<Account<'info, TrustList>>,
+    #[account(
+        mut, seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
     platform: Box<Account<'info, Platform>>,
     #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
     platform_fctr_token_vault: Account<'info, TokenAccount>,
@@ -33,89 +52,206 @@ pub struct GrantTokens<'info> {
     token_program: Program<'info, Token>,
 }
 
-pub fn grant_tokens(ctx: Context<GrantTokens>, amount: u64) -> Result<()> {
+/// Grants straight into the confidant's receipt, which takes the confidant's consent up front:
+/// the grantor has to be allowed on the confidant's trust list. Anyone else offers the grant
+/// for the confidant to accept.
+pub fn grant_tokens(ctx: Context<GrantTokens>, amount: u64, grantor_share_bps: u16) -> Result<()> {
+    if ctx.accounts.receipt.authority != ctx.accounts.authority.key() {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    check_trust_lists(&ctx.accounts.trust_list, &ctx.accounts.confidant_trust_list)?;
+    if !ctx
+        .accounts
+        .confidant_trust_list
+        .allows(&ctx.accounts.authority.key())
+    {
+        return err!(CustomErrors::TrustListViolation);
+    }
+    if ctx.accounts.authority.key() == ctx.accounts.confidant_authority.key()
+        || amount == 0
+        || amount >= ctx.accounts.fctr_vault.amount
+    {
+        return err!(CustomErrors::TokenGrantError);
+    }
     let now: u64 = Clock::get()?.unix_timestamp as _;
-    if (ctx.accounts.receipt.authority != ctx.accounts.authority.key())
-        || (ctx.accounts.confidant_receipt.authority != ctx.accounts.confidant_user.authority)
+    record_grant(
+        &mut ctx.accounts.platform,
+        &ctx.accounts.user,
+        &mut ctx.accounts.receipt,
+        ctx.accounts.fctr_vault.amount,
+        GrantConfidant {
+            user: &ctx.accounts.confidant_user,
+            receipt: &mut ctx.accounts.confidant_receipt,
+            grant_history: &mut ctx.accounts.confidant_grant_history,
+            reputation: &mut ctx.accounts.confidant_reputation,
+        },
+        GrantTerms {
+            amount,
+            grantor_share_bps,
+        },
+    )?;
+
+    record_grant_stats(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.confidant_user_stats,
+        amount,
+    );
+
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fctr_vault.to_account_info(),
+            to: ctx.accounts.platform_fctr_token_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    emit!(GrantEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        from: ctx.accounts.user.key(),
+        to: ctx.accounts.confidant_user.key(),
+        amount,
+        grantor_share_bps,
+        staked: ctx.accounts.confidant_receipt.is_valid
+            && ctx.accounts.confidant_receipt.round_ends > now,
+        grantor_fctr_balance: ctx.accounts.fctr_vault.amount - amount,
+        confidant_amount_deposited: ctx.accounts.confidant_receipt.amount_deposited,
+    });
+
+    Ok(())
+}
+
+/// Both the grantor and the confidant have to accept each other on their trust lists.
+pub(crate) fn check_trust_lists(
+    grantor_list: &TrustList,
+    confidant_list: &TrustList,
+) -> Result<()> {
+    if !grantor_list.permits(&confidant_list.authority)
+        || !confidant_list.permits(&grantor_list.authority)
     {
+        return err!(CustomErrors::TrustListViolation);
+    }
+    Ok(())
+}
+
+pub(crate) fn record_grant_stats(
+    grantor_stats: &mut UserStats,
+    confidant_stats: &mut UserStats,
+    amount: u64,
+) {
+    grantor_stats.grants_given += 1;
+    grantor_stats.fctr_granted += amount;
+    confidant_stats.grants_received += 1;
+    confidant_stats.fctr_received += amount;
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct GrantTerms {
+    pub amount: u64,
+    pub grantor_share_bps: u16,
+}
+
+/// The receiving side of a grant.
+pub(crate) struct GrantConfidant<'a, 'info> {
+    pub user: &'a User,
+    pub receipt: &'a mut Account<'info, Receipt>,
+    pub grant_history: &'a mut GrantHistory,
+    pub reputation: &'a mut Reputation,
+}
+
+/// Validates a grant and books it on the confidant's receipt and the grantor's APR.
+/// `grantor_balance` is the grantor's FCTR balance before the granted tokens left their vault.
+pub(crate) fn record_grant<'info>(
+    platform: &mut Platform,
+    user: &Account<'info, User>,
+    receipt: &mut Receipt,
+    grantor_balance: u64,
+    confidant: GrantConfidant<'_, 'info>,
+    terms: GrantTerms,
+) -> Result<()> {
+    let GrantConfidant {
+        user: confidant_user,
+        receipt: confidant_receipt,
+        grant_history,
+        reputation,
+    } = confidant;
+    let now: u64 = Clock::get()?.unix_timestamp as _;
+    if confidant_receipt.authority != confidant_user.authority {
         return err!(CustomErrors::InvalidReceiptAuthority);
     }
-    if !ctx.accounts.confidant_user.grant_program || !ctx.accounts.user.grant_program {
+    if !confidant_user.grant_program || !user.grant_program {
         return err!(CustomErrors::GrantProgramError);
     }
-    let grant_duration = if ctx.accounts.confidant_receipt.stake_ts > now {
-        ctx.accounts.platform.round_start + ctx.accounts.platform.round_duration - now
+    if !platform.is_valid_grantor_share(terms.grantor_share_bps) {
+        return err!(CustomErrors::InvalidGrantorShare);
+    }
+    if confidant_receipt.to_account_info().data_len() < 8 + platform.receipt_space() {
+        return err!(CustomErrors::ReceiptNotResized);
+    }
+    // Grants into an active stake are staked right away for the rest of the round,
+    // otherwise they wait for the confidant's next stake.
+    let is_staked = confidant_receipt.is_valid && confidant_receipt.round_ends > now;
+    let grant_duration = if is_staked {
+        confidant_receipt.round_ends - now
     } else {
-        ctx.accounts.platform.round_duration
+        platform.round_duration
     };
 
     msg!(
         "Stake: {}. Start: {}. End: {}. Now: {}",
-        ctx.accounts.confidant_receipt.stake_ts,
-        ctx.accounts.platform.round_start,
-        ctx.accounts.platform.round_start + ctx.accounts.platform.round_duration,
+        confidant_receipt.stake_ts,
+        platform.round_start,
+        platform.round_start + platform.round_duration,
         now
     );
-    let grantors_list = if ctx.accounts.confidant_receipt.round_ends > now {
-        &mut ctx.accounts.confidant_receipt.grantors
+    let grantors_list = if is_staked {
+        &mut confidant_receipt.grantors
     } else {
-        &mut ctx.accounts.confidant_receipt.next_round_grantors
+        &mut confidant_receipt.next_round_grantors
     };
 
-    let amount_ratio = ctx.accounts.user.user_fctr_amount as f64
-        / ctx.accounts.confidant_user.user_fctr_amount as f64;
-    if grantors_list
-        .iter()
-        .any(|g| g.grantor == ctx.accounts.fctr_vault.key())
-        || grantors_list.len() >= 4
-        || amount_ratio < 0.5
-        || 2.0 < amount_ratio
-    {
+    if grantors_list.iter().any(|g| g.grantor == user.key()) {
+        return err!(CustomErrors::TokensAlreadyGranted);
+    }
+    if grantors_list.len() >= platform.max_active_grantors as usize {
+        return err!(CustomErrors::GrantorsCountLimit);
+    }
+    let amount_ratio = user.user_fctr_amount as f64 / confidant_user.user_fctr_amount as f64;
+    if !(platform.min_grant_ratio..=platform.max_grant_ratio).contains(&amount_ratio) {
         return err!(CustomErrors::TokenGrantError);
     }
     grantors_list.push(GrantorRecord {
-        amount,
+        amount: terms.amount,
         grant_duration,
-        grantor: ctx.accounts.user.key(),
+        grantor_share_bps: terms.grantor_share_bps,
+        grantor: user.key(),
+        grantor_balance,
     });
-    if ctx
-        .accounts
-        .confidant_receipt
-        .grantors_history
-        .iter()
-        .any(|g| g.grantor == ctx.accounts.fctr_vault.key())
-    {
+    if grant_history.contains(&user.key()) {
         return err!(CustomErrors::GrantCooldown);
     }
 
-    ctx.accounts.receipt.apr = ctx.accounts.receipt.apr * ctx.accounts.fctr_vault.amount as f64
-        / (ctx.accounts.fctr_vault.amount - amount) as f64;
-
-    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.fctr_vault.to_account_info(),
-            to: ctx.accounts.platform_fctr_token_vault.to_account_info(),
-            authority: ctx.accounts.platform.to_account_info(),
-        },
-        signer,
-    );
-    token::transfer(cpi_ctx, amount)?;
+    if is_staked {
+        confidant_receipt.amount_deposited += terms.amount;
+        platform.total_fctr_staked += terms.amount;
+    } else {
+        platform.total_fctr_pending += terms.amount;
+    }
+    platform.total_fctr_granted += terms.amount;
 
-    ctx.accounts
-        .confidant_receipt
-        .grantors_history
-        .push(GrantorHistoryRecord {
-            grantor: ctx.accounts.user.key(),
-            grant_ts: now,
-        });
+    receipt.apr = rewards::grantor_apr(receipt.apr, grantor_balance, terms.amount);
 
-    emit!(GrantEvent {
-        from: ctx.accounts.user.key(),
-        to: ctx.accounts.confidant_user.key(),
-        amount,
+    grant_history.push(GrantorHistoryRecord {
+        grantor: user.key(),
+        grant_ts: now,
     });
+    reputation.record_grant(terms.amount);
 
     Ok(())
 }
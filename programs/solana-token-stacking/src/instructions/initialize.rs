@@ -1,6 +1,13 @@
+use crate::state::{
+    DEFAULT_GRANT_OFFER_TTL_ROUNDS, DEFAULT_MAX_ACTIVE_GRANTORS, DEFAULT_MAX_GRANTORS_HISTORY,
+    DEFAULT_MAX_GRANTOR_SHARE_BPS, DEFAULT_MAX_GRANT_RATIO, DEFAULT_MIN_GRANTOR_SHARE_BPS,
+    DEFAULT_MIN_GRANT_RATIO,
+};
 use crate::{
-    errors::CustomErrors, events::PlatformInitializeEvent, state::Platform, BCDEV_DECIMALS,
-    FCTR_DECIMALS,
+    errors::CustomErrors,
+    events::{EventHeader, PlatformInitializeEvent},
+    state::Platform,
+    BCDEV_DECIMALS, FCTR_DECIMALS,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
@@ -78,9 +85,21 @@ pub fn initialize(
 
     ctx.accounts.platform.round_duration = round_duration;
     ctx.accounts.platform.registration_price = registration_price;
+    ctx.accounts.platform.max_active_grantors = DEFAULT_MAX_ACTIVE_GRANTORS;
+    ctx.accounts.platform.max_grantors_history = DEFAULT_MAX_GRANTORS_HISTORY;
+    ctx.accounts.platform.min_grant_ratio = DEFAULT_MIN_GRANT_RATIO;
+    ctx.accounts.platform.max_grant_ratio = DEFAULT_MAX_GRANT_RATIO;
+    ctx.accounts.platform.reclaim_grace_period = round_duration;
+    ctx.accounts.platform.grant_offer_ttl_rounds = DEFAULT_GRANT_OFFER_TTL_ROUNDS;
+    ctx.accounts.platform.min_grantor_share_bps = DEFAULT_MIN_GRANTOR_SHARE_BPS;
+    ctx.accounts.platform.max_grantor_share_bps = DEFAULT_MAX_GRANTOR_SHARE_BPS;
     ctx.accounts.platform.authority = ctx.accounts.platform_authority.key();
 
-    emit!(PlatformInitializeEvent {});
+    emit!(PlatformInitializeEvent {
+        header: EventHeader::new(ctx.accounts.platform_authority.key(), 0)?,
+        round_duration,
+        registration_price,
+    });
 
     Ok(())
 }
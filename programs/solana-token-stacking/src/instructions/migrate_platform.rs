@@ -0,0 +1,70 @@
+use crate::instructions::resize_receipt::grow_account;
+use crate::state::{
+    DEFAULT_GRANT_OFFER_TTL_ROUNDS, DEFAULT_MAX_ACTIVE_GRANTORS, DEFAULT_MAX_GRANTORS_HISTORY,
+    DEFAULT_MAX_GRANTOR_SHARE_BPS, DEFAULT_MAX_GRANT_RATIO, DEFAULT_MIN_GRANTOR_SHARE_BPS,
+    DEFAULT_MIN_GRANT_RATIO, LEGACY_PLATFORM_SPACE,
+};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, PlatformMigratedEvent},
+    state::Platform,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigratePlatform<'info> {
+    /// CHECK: read by hand, a platform of the first version is too short for a `Platform`.
+    #[account(mut, owner = crate::ID, seeds = [b"platform"], bump)]
+    platform: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [b"sol_vault"], bump)]
+    sol_vault: AccountInfo<'info>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Grows a platform of the first version to the current layout, with the settings `initialize`
+/// starts with. The staking totals are rebuilt by `migrate_user`, one user at a time.
+pub fn migrate_platform(ctx: Context<MigratePlatform>) -> Result<()> {
+    let info = ctx.accounts.platform.to_account_info();
+    if info.data_len() != 8 + LEGACY_PLATFORM_SPACE {
+        return err!(CustomErrors::AccountAlreadyMigrated);
+    }
+    grow_account(
+        &info,
+        8 + Platform::SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+    info.try_borrow_mut_data()?[8 + LEGACY_PLATFORM_SPACE..].fill(0);
+    let mut platform = Platform::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    if platform.authority != ctx.accounts.authority.key() {
+        return err!(CustomErrors::UnauthorizedSigner);
+    }
+
+    platform.round_index = u64::from(platform.round_start > 0);
+    platform.max_active_grantors = DEFAULT_MAX_ACTIVE_GRANTORS;
+    platform.max_grantors_history = DEFAULT_MAX_GRANTORS_HISTORY;
+    platform.min_grant_ratio = DEFAULT_MIN_GRANT_RATIO;
+    platform.max_grant_ratio = DEFAULT_MAX_GRANT_RATIO;
+    platform.reclaim_grace_period = platform.round_duration;
+    platform.grant_offer_ttl_rounds = DEFAULT_GRANT_OFFER_TTL_ROUNDS;
+    platform.min_grantor_share_bps = DEFAULT_MIN_GRANTOR_SHARE_BPS;
+    platform.max_grantor_share_bps = DEFAULT_MAX_GRANTOR_SHARE_BPS;
+    // SOL paid out before can't be told apart any more, the vault counts as all paid in.
+    platform.total_sol_in = ctx
+        .accounts
+        .sol_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    let mut data: &mut [u8] = &mut info.try_borrow_mut_data()?;
+    platform.try_serialize(&mut data)?;
+
+    emit!(PlatformMigratedEvent {
+        header: EventHeader::new(ctx.accounts.authority.key(), platform.round_index)?,
+        total_sol_in: platform.total_sol_in,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::instructions::resize_receipt::grow_account;
+use crate::state::{
+    GrantHistory, LegacyReceipt, Receipt, Reputation, TrustList, UserStats, LEGACY_USER_SPACE,
+};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, UserMigratedEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MigrateUser<'info> {
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+    /// CHECK: read by hand, a user of the first version is too short for a `User`.
+    #[account(mut, owner = crate::ID, seeds = [b"user", authority.key().as_ref()], bump)]
+    user: UncheckedAccount<'info>,
+    /// CHECK: read by hand, a receipt of the first version has another layout.
+    #[account(mut, owner = crate::ID, seeds = [b"receipt", authority.key().as_ref()], bump)]
+    receipt: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"grant_history", authority.key().as_ref()],
+        bump,
+        space = 8 + platform.grant_history_space(),
+    )]
+    grant_history: Box<Account<'info, GrantHistory>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump,
+        space = 8 + Reputation::SPACE,
+    )]
+    reputation: Box<Account<'info, Reputation>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"user_stats", authority.key().as_ref()],
+        bump,
+        space = 8 + UserStats::SPACE,
+    )]
+    user_stats: Box<Account<'info, UserStats>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"trust_list", authority.key().as_ref()],
+        bump,
+        space = 8 + TrustList::SPACE,
+    )]
+    trust_list: Box<Account<'info, TrustList>>,
+    /// CHECK: gets back the rent the smaller receipt no longer needs.
+    #[account(mut)]
+    authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Brings a user of the first version to the current layout: grows the user, converts the
+/// receipt and moves its grant history out, and creates the accounts `register_user` creates
+/// since. The user's stake and grants are added to the platform totals. Needs the platform to
+/// be migrated first.
+pub fn migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+    let user_info = ctx.accounts.user.to_account_info();
+    let user_migrated = user_info.data_len() == 8 + LEGACY_USER_SPACE;
+    if user_migrated {
+        grow_account(
+            &user_info,
+            8 + User::SPACE,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+        user_info.try_borrow_mut_data()?[8 + LEGACY_USER_SPACE..].fill(0);
+    }
+    // The seeds already tie the user to the authority, this only checks what was grown.
+    User::try_deserialize(&mut &user_info.try_borrow_data()?[..])?;
+
+    // Accounts `init_if_needed` just created still have a default authority.
+    let authority = ctx.accounts.authority.key();
+    let mut created = false;
+    if ctx.accounts.grant_history.authority == Pubkey::default() {
+        ctx.accounts.grant_history.bump = ctx.bumps["grant_history"];
+        ctx.accounts.grant_history.authority = authority;
+        ctx.accounts
+            .grant_history
+            .resize(ctx.accounts.platform.max_grantors_history as usize);
+        created = true;
+    }
+    if ctx.accounts.reputation.authority == Pubkey::default() {
+        ctx.accounts.reputation.bump = ctx.bumps["reputation"];
+        ctx.accounts.reputation.authority = authority;
+        created = true;
+    }
+    if ctx.accounts.user_stats.authority == Pubkey::default() {
+        ctx.accounts.user_stats.bump = ctx.bumps["user_stats"];
+        ctx.accounts.user_stats.sol_paid = ctx.accounts.platform.registration_price;
+        ctx.accounts.user_stats.authority = authority;
+        created = true;
+    }
+    if ctx.accounts.trust_list.authority == Pubkey::default() {
+        ctx.accounts.trust_list.bump = ctx.bumps["trust_list"];
+        ctx.accounts.trust_list.authority = authority;
+        created = true;
+    }
+
+    let receipt_info = ctx.accounts.receipt.to_account_info();
+    let receipt_migrated = receipt_info.data_len() == 8 + LegacyReceipt::SPACE;
+    let mut history_records = 0;
+    if receipt_migrated {
+        let legacy = LegacyReceipt::deserialize(&mut &receipt_info.try_borrow_data()?[8..])?;
+        let (receipt, history) = legacy.migrate();
+        let grantors = receipt
+            .grantors
+            .len()
+            .max(receipt.next_round_grantors.len())
+            .max(ctx.accounts.platform.max_active_grantors as usize);
+        let new_len = 8 + Receipt::space(grantors);
+        receipt_info.realloc(new_len, false)?;
+        let surplus = receipt_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(new_len));
+        **receipt_info.try_borrow_mut_lamports()? -= surplus;
+        **ctx.accounts.authority.try_borrow_mut_lamports()? += surplus;
+        let mut data: &mut [u8] = &mut receipt_info.try_borrow_mut_data()?;
+        receipt.try_serialize(&mut data)?;
+
+        history_records = history.len() as u32;
+        history
+            .into_iter()
+            .for_each(|record| ctx.accounts.grant_history.push(record));
+
+        let next_granted = receipt
+            .next_round_grantors
+            .iter()
+            .fold(0, |sum, g| sum + g.amount);
+        let platform = &mut ctx.accounts.platform;
+        if receipt.is_valid {
+            platform.active_stakers += 1;
+            platform.total_fctr_staked += receipt.amount_deposited;
+            platform.total_fctr_granted += receipt.grantors.iter().fold(0, |sum, g| sum + g.amount);
+        }
+        platform.total_fctr_pending += next_granted;
+        platform.total_fctr_granted += next_granted;
+    }
+
+    if !user_migrated && !receipt_migrated && !created {
+        return err!(CustomErrors::AccountAlreadyMigrated);
+    }
+
+    emit!(UserMigratedEvent {
+        header: EventHeader::new(ctx.accounts.payer.key(), ctx.accounts.platform.round_index)?,
+        owner: authority,
+        receipt_migrated,
+        history_records,
+    });
+
+    Ok(())
+}
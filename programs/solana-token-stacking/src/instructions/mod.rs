@@ -1,25 +1,71 @@
+pub use accept_grant::*;
 pub use add_liquidity::*;
+pub use audit_platform::*;
 pub use buy_tokens::*;
 pub use claim_tokens::*;
+pub use close_position::*;
 pub use grant_tokens::*;
 pub use initialize::*;
+pub use migrate_platform::*;
+pub use migrate_user::*;
+pub use offer_grant::*;
+pub use open_position::*;
+pub use project_reward::*;
+pub use prove_voting_weight::*;
+pub use quote_tokens::*;
+pub use reclaim_grant::*;
 pub use register_user::*;
+pub use reject_grant::*;
+pub use resize_receipt::*;
+pub use revoke_grant::*;
 pub use sell_bcdev_tokens::*;
 pub use sell_fctr_tokens::*;
+pub use set_operator::*;
+pub use set_paused::*;
+pub use snapshot::*;
 pub use stake::*;
 pub use start_round::*;
 pub use unstake::*;
+pub use update_grant_limits::*;
+pub use update_grant_offer_ttl::*;
+pub use update_grantor_share_bounds::*;
+pub use update_reclaim_grace_period::*;
+pub use update_trust_list::*;
+pub use user_summary::*;
 pub use withdraw::*;
 
+pub mod accept_grant;
 pub mod add_liquidity;
+pub mod audit_platform;
 pub mod buy_tokens;
 pub mod claim_tokens;
+pub mod close_position;
 pub mod grant_tokens;
 pub mod initialize;
+pub mod migrate_platform;
+pub mod migrate_user;
+pub mod offer_grant;
+pub mod open_position;
+pub mod project_reward;
+pub mod prove_voting_weight;
+pub mod quote_tokens;
+pub mod reclaim_grant;
 pub mod register_user;
+pub mod reject_grant;
+pub mod resize_receipt;
+pub mod revoke_grant;
 pub mod sell_bcdev_tokens;
 pub mod sell_fctr_tokens;
+pub mod set_operator;
+pub mod set_paused;
+pub mod snapshot;
 pub mod stake;
 pub mod start_round;
 pub mod unstake;
+pub mod update_grant_limits;
+pub mod update_grant_offer_ttl;
+pub mod update_grantor_share_bounds;
+pub mod update_reclaim_grace_period;
+pub mod update_trust_list;
+pub mod user_summary;
 pub mod withdraw;
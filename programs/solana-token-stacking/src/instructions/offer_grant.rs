@@ -0,0 +1,110 @@
+use crate::instructions::grant_tokens::check_trust_lists;
+use crate::state::{GrantOffer, TrustList};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, GrantOfferEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct OfferGrant<'info> {
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
+    fctr_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = user.authority)]
+    authority: Signer<'info>,
+    #[account(seeds = [b"user", confidant_authority.key().as_ref()], bump = confidant_user.bump)]
+    confidant_user: Account<'info, User>,
+    /// CHECK:
+    confidant_authority: UncheckedAccount<'info>,
+    #[account(seeds = [b"trust_list", authority.key().as_ref()], bump = trust_list.bump)]
+    trust_list: Box<Account<'info, TrustList>>,
+    #[account(seeds = [b"trust_list", confidant_authority.key().as_ref()], bump = confidant_trust_list.bump)]
+    confidant_trust_list: Box<Account<'info, TrustList>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"grant_offer", authority.key().as_ref(), confidant_authority.key().as_ref()],
+        bump,
+        space = 8 + GrantOffer::SPACE,
+    )]
+    grant_offer: Account<'info, GrantOffer>,
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
+    platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
+    platform_fctr_token_vault: Account<'info, TokenAccount>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn offer_grant(ctx: Context<OfferGrant>, amount: u64, grantor_share_bps: u16) -> Result<()> {
+    if !ctx.accounts.confidant_user.grant_program || !ctx.accounts.user.grant_program {
+        return err!(CustomErrors::GrantProgramError);
+    }
+    check_trust_lists(&ctx.accounts.trust_list, &ctx.accounts.confidant_trust_list)?;
+    if !ctx
+        .accounts
+        .platform
+        .is_valid_grantor_share(grantor_share_bps)
+    {
+        return err!(CustomErrors::InvalidGrantorShare);
+    }
+    if ctx.accounts.authority.key() == ctx.accounts.confidant_authority.key()
+        || amount == 0
+        || amount >= ctx.accounts.fctr_vault.amount
+    {
+        return err!(CustomErrors::TokenGrantError);
+    }
+
+    // Offered tokens are held by the platform until the confidant accepts or rejects them.
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fctr_vault.to_account_info(),
+            to: ctx.accounts.platform_fctr_token_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    ctx.accounts.platform.total_fctr_offered += amount;
+
+    let grant_offer = &mut ctx.accounts.grant_offer;
+    grant_offer.bump = *ctx
+        .bumps
+        .get("grant_offer")
+        .ok_or(CustomErrors::EmptyBump)?;
+    grant_offer.amount = amount;
+    grant_offer.grantor_share_bps = grantor_share_bps;
+    grant_offer.created_round = ctx.accounts.platform.round_index;
+    grant_offer.expires_round =
+        ctx.accounts.platform.round_index + ctx.accounts.platform.grant_offer_ttl_rounds;
+    grant_offer.grantor = ctx.accounts.authority.key();
+    grant_offer.confidant = ctx.accounts.confidant_authority.key();
+
+    emit!(GrantOfferEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        from: ctx.accounts.user.key(),
+        to: ctx.accounts.confidant_user.key(),
+        amount,
+        grantor_share_bps,
+        expires_round: grant_offer.expires_round,
+        grantor_fctr_balance: ctx.accounts.fctr_vault.amount - amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::state::{Position, MAX_LOCK_ROUNDS};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, PositionOpenedEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+const POSITION_APR: f64 = 0.01;
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(mut, seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"position", authority.key().as_ref(), user.next_position_id.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + Position::SPACE,
+    )]
+    position: Account<'info, Position>,
+    #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
+    fctr_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = user.authority)]
+    authority: Signer<'info>,
+    #[account(
+        mut, seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
+    platform: Account<'info, Platform>,
+    #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
+    platform_fctr_token_vault: Account<'info, TokenAccount>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn open_position(ctx: Context<OpenPosition>, amount: u64, lock_rounds: u8) -> Result<()> {
+    let now: u64 = Clock::get()?.unix_timestamp as _;
+    if ctx.accounts.platform.round_start + ctx.accounts.platform.round_duration < now {
+        return err!(CustomErrors::NoActiveRound);
+    }
+    if lock_rounds > MAX_LOCK_ROUNDS {
+        return err!(CustomErrors::InvalidLockTier);
+    }
+    if amount == 0 || amount > ctx.accounts.user.user_fctr_amount {
+        return err!(CustomErrors::InvalidPositionAmount);
+    }
+
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.fctr_vault.to_account_info(),
+            to: ctx.accounts.platform_fctr_token_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    let round_ends = ctx.accounts.platform.round_start + ctx.accounts.platform.round_duration;
+    let position = &mut ctx.accounts.position;
+    position.bump = *ctx.bumps.get("position").ok_or(CustomErrors::EmptyBump)?;
+    position.id = ctx.accounts.user.next_position_id;
+    position.lock_rounds = lock_rounds;
+    position.stake_ts = now;
+    position.stake_duration = round_ends - now;
+    position.round_ends = round_ends;
+    position.unlock_ts = round_ends + lock_rounds as u64 * ctx.accounts.platform.round_duration;
+    position.amount_deposited = amount;
+    position.apr = POSITION_APR;
+    position.authority = ctx.accounts.authority.key();
+
+    ctx.accounts.user.user_fctr_amount -= amount;
+    ctx.accounts.user.next_position_id += 1;
+    ctx.accounts.platform.total_fctr_staked += amount;
+
+    emit!(PositionOpenedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        owner: ctx.accounts.authority.key(),
+        id: position.id,
+        amount,
+        lock_rounds,
+        unlock_ts: position.unlock_ts,
+    });
+
+    Ok(())
+}
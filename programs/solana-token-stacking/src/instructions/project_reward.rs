@@ -0,0 +1,69 @@
+use crate::rewards::{self, Grant};
+use crate::{
+    errors::CustomErrors,
+    events::GrantorReturn,
+    state::{Platform, Receipt, RewardProjection, User},
+};
+use anchor_lang::{prelude::*, solana_program::program::set_return_data};
+use itertools::Itertools;
+
+#[derive(Accounts)]
+pub struct ProjectReward<'info> {
+    #[account(seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
+    receipt: Account<'info, Receipt>,
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    /// CHECK:
+    authority: UncheckedAccount<'info>,
+    #[account(seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+}
+
+pub fn project_reward(ctx: Context<ProjectReward>) -> Result<()> {
+    if ctx.accounts.receipt.authority != ctx.accounts.authority.key() {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    let projection =
+        project_receipt_reward(&ctx.accounts.receipt, ctx.accounts.platform.round_duration);
+    set_return_data(&projection.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Splits the reward the same way `unstake` would with the grants currently on the receipt.
+pub(crate) fn project_receipt_reward(receipt: &Receipt, round_duration: u64) -> RewardProjection {
+    if !receipt.is_valid {
+        return RewardProjection::default();
+    }
+    let grantors = receipt
+        .grantors
+        .iter()
+        .sorted_by_key(|g| g.grant_duration)
+        .collect::<Vec<_>>();
+    let grants = grantors.iter().map(|g| Grant::from(*g)).collect::<Vec<_>>();
+    let total_reward = rewards::total_reward(
+        receipt.amount_deposited,
+        receipt.apr,
+        receipt.stake_duration,
+        &grants,
+        round_duration,
+    );
+    let split = rewards::split_reward(total_reward, &grants);
+    let grantor_returns = grantors
+        .iter()
+        .zip(split.grantor_rewards)
+        .map(|(g, reward)| GrantorReturn {
+            grantor: g.grantor,
+            amount: g.amount,
+            reward,
+        })
+        .collect();
+
+    RewardProjection {
+        amount_deposited: receipt.amount_deposited,
+        round_ends: receipt.round_ends,
+        total_reward,
+        staker_reward: split.staker_reward,
+        grantor_returns,
+    }
+}
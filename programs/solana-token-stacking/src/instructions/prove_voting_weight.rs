@@ -0,0 +1,28 @@
+use crate::state::{Snapshot, VotingWeight, VotingWeightProof};
+use anchor_lang::{prelude::*, solana_program::program::set_return_data};
+
+#[derive(Accounts)]
+#[instruction(round_index: u64)]
+pub struct ProveVotingWeight<'info> {
+    #[account(seeds = [b"snapshot", round_index.to_le_bytes().as_ref()], bump = snapshot.bump)]
+    snapshot: Account<'info, Snapshot>,
+    #[account(
+        seeds = [b"voting_weight", round_index.to_le_bytes().as_ref(), authority.key().as_ref()],
+        bump = voting_weight.bump,
+    )]
+    voting_weight: Account<'info, VotingWeight>,
+    /// CHECK:
+    authority: UncheckedAccount<'info>,
+}
+
+pub fn prove_voting_weight(ctx: Context<ProveVotingWeight>, round_index: u64) -> Result<()> {
+    let proof = VotingWeightProof {
+        round_index,
+        weight: ctx.accounts.voting_weight.weight,
+        total_weight: ctx.accounts.snapshot.total_weight,
+        authority: ctx.accounts.authority.key(),
+    };
+    set_return_data(&proof.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::{errors::CustomErrors, pricing, state::TradeQuote};
+use anchor_lang::{prelude::*, solana_program::program::set_return_data};
+
+#[derive(Accounts)]
+pub struct QuoteTokens {}
+
+/// FCTR that `buy_tokens` would mint for `lamports`.
+pub fn quote_buy(_ctx: Context<QuoteTokens>, lamports: u64) -> Result<()> {
+    let fctr_count = pricing::fctr_for_lamports(lamports).ok_or(CustomErrors::BuyAmountTooLarge)?;
+    if fctr_count < 10 {
+        return err!(CustomErrors::InvalidBuyAmount);
+    }
+    return_quote(lamports, fctr_count)
+}
+
+/// Lamports that selling `amount` FCTR would pay out.
+pub fn quote_sell_fctr(_ctx: Context<QuoteTokens>, amount: u64) -> Result<()> {
+    return_quote(amount, pricing::lamports_for_fctr(amount))
+}
+
+/// Lamports that selling `amount` BCDEV would pay out.
+pub fn quote_sell_bcdev(_ctx: Context<QuoteTokens>, amount: u64) -> Result<()> {
+    return_quote(amount, pricing::lamports_for_bcdev(amount))
+}
+
+fn return_quote(amount_in: u64, amount_out: u64) -> Result<()> {
+    let quote = TradeQuote {
+        amount_in,
+        amount_out,
+    };
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::rewards::{self, Grant};
+use crate::state::{Receipt, Reputation, UserStats};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, ReclaimGrantEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, MintTo, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct ReclaimGrant<'info> {
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
+    fctr_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"bcdev_vault", authority.key().as_ref()], bump = user.bump_bcdev_vault)]
+    bcdev_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
+    #[account(mut, address = user.authority)]
+    authority: Signer<'info>,
+    #[account(seeds = [b"user", confidant_authority.key().as_ref()], bump = confidant_user.bump)]
+    confidant_user: Account<'info, User>,
+    #[account(mut, seeds = [b"receipt", confidant_authority.key().as_ref()], bump = confidant_user.bump_receipt)]
+    confidant_receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"reputation", confidant_authority.key().as_ref()], bump = confidant_reputation.bump)]
+    confidant_reputation: Box<Account<'info, Reputation>>,
+    /// CHECK:
+    confidant_authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
+    platform_fctr_token_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"bcdev_mint"], bump = platform.bump_bcdev_mint)]
+    bcdev_mint: Account<'info, Mint>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn reclaim_grant(ctx: Context<ReclaimGrant>) -> Result<()> {
+    let now: u64 = Clock::get()?.unix_timestamp as _;
+    if ctx.accounts.confidant_receipt.authority != ctx.accounts.confidant_authority.key() {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    if !ctx.accounts.confidant_receipt.is_valid
+        || now
+            <= ctx.accounts.confidant_receipt.round_ends
+                + ctx.accounts.platform.reclaim_grace_period
+    {
+        return err!(CustomErrors::ReclaimTooEarly);
+    }
+    let grantor = *ctx
+        .accounts
+        .confidant_receipt
+        .grantors
+        .iter()
+        .find(|g| g.grantor == ctx.accounts.user.key())
+        .ok_or(CustomErrors::GrantorNotFound)?;
+
+    let reward = rewards::reclaimed_grant_reward(
+        ctx.accounts.confidant_receipt.apr,
+        Grant::from(&grantor),
+        ctx.accounts.platform.round_duration,
+    );
+
+    ctx.accounts
+        .confidant_receipt
+        .grantors
+        .retain(|g| g.grantor != ctx.accounts.user.key());
+    ctx.accounts.confidant_receipt.amount_deposited -= grantor.amount;
+    ctx.accounts.platform.total_fctr_staked -= grantor.amount;
+    ctx.accounts.platform.total_fctr_granted -= grantor.amount;
+    let round_ends = ctx.accounts.confidant_receipt.round_ends;
+    ctx.accounts
+        .confidant_reputation
+        .record_missed_unstake(round_ends);
+
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.platform_fctr_token_vault.to_account_info(),
+            to: ctx.accounts.fctr_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, grantor.amount)?;
+
+    let mint_cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.bcdev_mint.to_account_info(),
+            to: ctx.accounts.bcdev_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::mint_to(mint_cpi_ctx, reward)?;
+    ctx.accounts.platform.bcdev_token_total_amount += reward;
+    ctx.accounts.platform.total_bcdev_rewards += reward;
+    ctx.accounts.user_stats.bcdev_earned += reward;
+
+    emit!(ReclaimGrantEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        from: ctx.accounts.user.key(),
+        to: ctx.accounts.confidant_user.key(),
+        amount: grantor.amount,
+        reward,
+        grantor_fctr_balance: ctx.accounts.fctr_vault.amount + grantor.amount,
+        grantor_bcdev_balance: ctx.accounts.bcdev_vault.amount + reward,
+        confidant_amount_deposited: ctx.accounts.confidant_receipt.amount_deposited,
+    });
+
+    Ok(())
+}
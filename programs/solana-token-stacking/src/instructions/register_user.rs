@@ -1,6 +1,9 @@
 use crate::errors::CustomErrors;
-use crate::state::{Platform, Receipt};
-use crate::{events::UserRegisteredEvent, state::User};
+use crate::state::{GrantHistory, Platform, Receipt, Reputation, TrustList, UserStats};
+use crate::{
+    events::{EventHeader, UserRegisteredEvent},
+    state::User,
+};
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke, system_instruction},
@@ -46,9 +49,41 @@ pub struct RegisterUser<'info> {
         payer = authority,
         seeds = [b"receipt", authority.key().as_ref()],
         bump,
-        space = 8 + Receipt::SPACE,
+        space = 8 + platform.receipt_space(),
     )]
     receipt: Account<'info, Receipt>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"grant_history", authority.key().as_ref()],
+        bump,
+        space = 8 + platform.grant_history_space(),
+    )]
+    grant_history: Box<Account<'info, GrantHistory>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump,
+        space = 8 + Reputation::SPACE,
+    )]
+    reputation: Box<Account<'info, Reputation>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"user_stats", authority.key().as_ref()],
+        bump,
+        space = 8 + UserStats::SPACE,
+    )]
+    user_stats: Box<Account<'info, UserStats>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"trust_list", authority.key().as_ref()],
+        bump,
+        space = 8 + TrustList::SPACE,
+    )]
+    trust_list: Box<Account<'info, TrustList>>,
     #[account(mut)]
     authority: Signer<'info>,
     /// CHECK:
@@ -72,6 +107,22 @@ pub fn register_user(ctx: Context<RegisterUser>, participate_in_grant_program: b
     ctx.accounts.user.grant_program = participate_in_grant_program;
     ctx.accounts.receipt.authority = ctx.accounts.authority.key();
     ctx.accounts.receipt.apr = 0.01;
+    ctx.accounts.grant_history.bump = *ctx
+        .bumps
+        .get("grant_history")
+        .ok_or(CustomErrors::EmptyBump)?;
+    ctx.accounts
+        .grant_history
+        .resize(ctx.accounts.platform.max_grantors_history as usize);
+    ctx.accounts.grant_history.authority = ctx.accounts.authority.key();
+    ctx.accounts.reputation.bump = *ctx.bumps.get("reputation").ok_or(CustomErrors::EmptyBump)?;
+    ctx.accounts.reputation.authority = ctx.accounts.authority.key();
+    ctx.accounts.user_stats.bump = *ctx.bumps.get("user_stats").ok_or(CustomErrors::EmptyBump)?;
+    ctx.accounts.user_stats.sol_paid = ctx.accounts.platform.registration_price;
+    ctx.accounts.platform.total_sol_in += ctx.accounts.platform.registration_price;
+    ctx.accounts.user_stats.authority = ctx.accounts.authority.key();
+    ctx.accounts.trust_list.bump = *ctx.bumps.get("trust_list").ok_or(CustomErrors::EmptyBump)?;
+    ctx.accounts.trust_list.authority = ctx.accounts.authority.key();
 
     invoke(
         &system_instruction::transfer(
@@ -86,7 +137,12 @@ pub fn register_user(ctx: Context<RegisterUser>, participate_in_grant_program: b
     )?;
 
     emit!(UserRegisteredEvent {
-        user: ctx.accounts.authority.key()
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        grant_program: participate_in_grant_program,
+        registration_price: ctx.accounts.platform.registration_price,
     });
 
     Ok(())
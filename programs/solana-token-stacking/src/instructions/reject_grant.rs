@@ -0,0 +1,72 @@
+use crate::state::GrantOffer;
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, GrantOfferRejectedEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct RejectGrant<'info> {
+    #[account(seeds = [b"user", grantor_authority.key().as_ref()], bump = grantor_user.bump)]
+    grantor_user: Account<'info, User>,
+    #[account(mut, seeds = [b"fctr_vault", grantor_authority.key().as_ref()], bump = grantor_user.bump_fctr_vault)]
+    grantor_fctr_vault: Account<'info, TokenAccount>,
+    /// CHECK:
+    #[account(mut)]
+    grantor_authority: UncheckedAccount<'info>,
+    #[account(seeds = [b"user", confidant_authority.key().as_ref()], bump = confidant_user.bump)]
+    confidant_user: Account<'info, User>,
+    /// CHECK:
+    confidant_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = grantor_authority,
+        seeds = [b"grant_offer", grantor_authority.key().as_ref(), confidant_authority.key().as_ref()],
+        bump = grant_offer.bump,
+    )]
+    grant_offer: Account<'info, GrantOffer>,
+    signer: Signer<'info>,
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
+    platform_fctr_token_vault: Account<'info, TokenAccount>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn reject_grant(ctx: Context<RejectGrant>) -> Result<()> {
+    // The confidant can reject an offer at any time, the grantor can take back an expired one.
+    let expired = ctx.accounts.platform.round_index > ctx.accounts.grant_offer.expires_round;
+    if ctx.accounts.signer.key() != ctx.accounts.confidant_authority.key()
+        && !(expired && ctx.accounts.signer.key() == ctx.accounts.grantor_authority.key())
+    {
+        return err!(CustomErrors::UnauthorizedSigner);
+    }
+    let amount = ctx.accounts.grant_offer.amount;
+
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.platform_fctr_token_vault.to_account_info(),
+            to: ctx.accounts.grantor_fctr_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+    ctx.accounts.platform.total_fctr_offered -= amount;
+
+    emit!(GrantOfferRejectedEvent {
+        header: EventHeader::new(ctx.accounts.signer.key(), ctx.accounts.platform.round_index)?,
+        from: ctx.accounts.grantor_user.key(),
+        to: ctx.accounts.confidant_user.key(),
+        amount,
+        expired,
+        grantor_fctr_balance: ctx.accounts.grantor_fctr_vault.amount + amount,
+    });
+
+    Ok(())
+}
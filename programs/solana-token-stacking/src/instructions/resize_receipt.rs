@@ -0,0 +1,90 @@
+use crate::state::{GrantHistory, Receipt};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, ReceiptResizedEvent},
+    state::{Platform, User},
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction},
+};
+
+#[derive(Accounts)]
+pub struct ResizeReceipt<'info> {
+    #[account(seeds = [b"platform"], bump = platform.bump)]
+    platform: Account<'info, Platform>,
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(mut, seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
+    receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"grant_history", authority.key().as_ref()], bump = grant_history.bump)]
+    grant_history: Box<Account<'info, GrantHistory>>,
+    /// CHECK:
+    #[account(address = user.authority)]
+    authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Grows the receipt and the grant history of a user to the current grant limits.
+pub fn resize_receipt(ctx: Context<ResizeReceipt>) -> Result<()> {
+    let receipt_len = 8 + ctx.accounts.platform.receipt_space();
+    let history_capacity = ctx.accounts.platform.max_grantors_history as usize;
+    let receipt_resized = grow_account(
+        &ctx.accounts.receipt.to_account_info(),
+        receipt_len,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    let history_resized = ctx.accounts.grant_history.capacity() < history_capacity
+        && grow_account(
+            &ctx.accounts.grant_history.to_account_info(),
+            8 + ctx.accounts.platform.grant_history_space(),
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+    if !receipt_resized && !history_resized {
+        return err!(CustomErrors::ReceiptAlreadySized);
+    }
+    if history_resized {
+        ctx.accounts.grant_history.resize(history_capacity);
+    }
+
+    emit!(ReceiptResizedEvent {
+        header: EventHeader::new(ctx.accounts.payer.key(), ctx.accounts.platform.round_index)?,
+        owner: ctx.accounts.authority.key(),
+        size: ctx.accounts.receipt.to_account_info().data_len() as u64,
+        grant_history_capacity: ctx.accounts.grant_history.capacity() as u32,
+    });
+
+    Ok(())
+}
+
+/// Tops up the rent and reallocates the account if it's smaller than `new_len`.
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<bool> {
+    if account.data_len() >= new_len {
+        return Ok(false);
+    }
+
+    let rent_diff = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_diff > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, rent_diff),
+            &[
+                payer.to_account_info(),
+                account.clone(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+    account.realloc(new_len, false)?;
+    Ok(true)
+}
@@ -0,0 +1,101 @@
+use crate::rewards;
+use crate::state::{GrantHistory, Receipt, Reputation};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, RevokeGrantEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct RevokeGrant<'info> {
+    #[account(mut, seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
+    receipt: Account<'info, Receipt>,
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
+    fctr_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = user.authority)]
+    authority: Signer<'info>,
+    #[account(seeds = [b"user", confidant_authority.key().as_ref()], bump = confidant_user.bump)]
+    confidant_user: Account<'info, User>,
+    #[account(mut, seeds = [b"receipt", confidant_authority.key().as_ref()], bump = confidant_user.bump_receipt)]
+    confidant_receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"grant_history", confidant_authority.key().as_ref()], bump = confidant_grant_history.bump)]
+    confidant_grant_history: Box<Account<'info, GrantHistory>>,
+    #[account(mut, seeds = [b"reputation", confidant_authority.key().as_ref()], bump = confidant_reputation.bump)]
+    confidant_reputation: Box<Account<'info, Reputation>>,
+    /// CHECK:
+    confidant_authority: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+    #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
+    platform_fctr_token_vault: Account<'info, TokenAccount>,
+    system_program: Program<'info, System>,
+    token_program: Program<'info, Token>,
+}
+
+pub fn revoke_grant(ctx: Context<RevokeGrant>) -> Result<()> {
+    if (ctx.accounts.receipt.authority != ctx.accounts.authority.key())
+        || (ctx.accounts.confidant_receipt.authority != ctx.accounts.confidant_authority.key())
+    {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    // Only grants that are still waiting for the confidant's next stake can be revoked.
+    let grant = *ctx
+        .accounts
+        .confidant_receipt
+        .next_round_grantors
+        .iter()
+        .find(|g| g.grantor == ctx.accounts.user.key())
+        .ok_or(CustomErrors::GrantorNotFound)?;
+    let granted_amount = grant.amount;
+
+    ctx.accounts
+        .confidant_receipt
+        .next_round_grantors
+        .retain(|g| g.grantor != ctx.accounts.user.key());
+    // A revoked grant is undone completely, so it doesn't put the grantor on cooldown either.
+    ctx.accounts
+        .confidant_grant_history
+        .retain(|g| g.grantor != ctx.accounts.user.key());
+    ctx.accounts
+        .confidant_reputation
+        .revoke_grant(granted_amount);
+    ctx.accounts.platform.total_fctr_granted -= granted_amount;
+    ctx.accounts.platform.total_fctr_pending -= granted_amount;
+
+    // Reverse the APR bump granted in `grant_tokens`.
+    ctx.accounts.receipt.apr = rewards::revoked_grantor_apr(
+        ctx.accounts.receipt.apr,
+        grant.grantor_balance,
+        granted_amount,
+    );
+
+    let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.platform_fctr_token_vault.to_account_info(),
+            to: ctx.accounts.fctr_vault.to_account_info(),
+            authority: ctx.accounts.platform.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, granted_amount)?;
+
+    emit!(RevokeGrantEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        from: ctx.accounts.user.key(),
+        to: ctx.accounts.confidant_user.key(),
+        amount: granted_amount,
+        grantor_fctr_balance: ctx.accounts.fctr_vault.amount + granted_amount,
+    });
+
+    Ok(())
+}
@@ -1,14 +1,14 @@
 use crate::{
-    events::SellBcdevTokensEvent,
-    state::{Platform, User},
-    BCDEV_DECIMALS,
+    errors::CustomErrors,
+    events::{EventHeader, SellBcdevTokensEvent},
+    pricing,
+    state::{Platform, User, UserStats},
 };
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke_signed, system_instruction},
 };
 use anchor_spl::token;
-use anchor_spl::token::spl_token::native_mint::DECIMALS;
 use anchor_spl::token::{Burn, Mint, Token, TokenAccount};
 
 #[derive(Accounts)]
@@ -17,10 +17,16 @@ pub struct SellBcdevTokens<'info> {
     user: Account<'info, User>,
     #[account(mut, seeds = [b"bcdev_vault", authority.key().as_ref()], bump = user.bump_bcdev_vault)]
     bcdev_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
     /// CHECK:
     #[account(mut, seeds = [b"sol_vault"], bump = platform.bump_sol_vault)]
     sol_vault: AccountInfo<'info>,
-    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    #[account(
+        mut, seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
     platform: Account<'info, Platform>,
     #[account(mut, seeds = [b"bcdev_mint"], bump = platform.bump_bcdev_mint)]
     bcdev_mint: Account<'info, Mint>,
@@ -31,8 +37,7 @@ pub struct SellBcdevTokens<'info> {
 }
 
 pub fn sell_bcdev_tokens(ctx: Context<SellBcdevTokens>, amount: u64) -> Result<()> {
-    let lamports_to_get =
-        ctx.accounts.bcdev_vault.amount / (11 * 10u64.pow((BCDEV_DECIMALS - DECIMALS) as _));
+    let lamports_to_get = pricing::lamports_for_bcdev(amount);
     invoke_signed(
         &system_instruction::transfer(
             ctx.accounts.sol_vault.key,
@@ -57,9 +62,21 @@ pub fn sell_bcdev_tokens(ctx: Context<SellBcdevTokens>, amount: u64) -> Result<(
         signer,
     );
     ctx.accounts.platform.bcdev_token_total_amount -= amount;
+    ctx.accounts.user_stats.bcdev_sold += amount;
+    ctx.accounts.user_stats.sol_received += lamports_to_get;
+    ctx.accounts.platform.total_sol_out += lamports_to_get;
     token::burn(cpi_ctx, amount)?;
 
-    emit!(SellBcdevTokensEvent { amount });
+    emit!(SellBcdevTokensEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        amount,
+        lamports: lamports_to_get,
+        bcdev_balance: ctx.accounts.bcdev_vault.amount - amount,
+        bcdev_total_amount: ctx.accounts.platform.bcdev_token_total_amount,
+    });
 
     Ok(())
 }
@@ -1,14 +1,14 @@
 use crate::{
-    events::SellFctrTokensEvent,
-    state::{Platform, User},
-    FCTR_DECIMALS,
+    errors::CustomErrors,
+    events::{EventHeader, SellFctrTokensEvent},
+    pricing,
+    state::{Platform, User, UserStats},
 };
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke_signed, system_instruction},
 };
 use anchor_spl::token;
-use anchor_spl::token::spl_token::native_mint::DECIMALS;
 use anchor_spl::token::{Burn, Mint, Token, TokenAccount};
 
 #[derive(Accounts)]
@@ -17,10 +17,16 @@ pub struct SellFctrTokens<'info> {
     user: Account<'info, User>,
     #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
     fctr_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
     /// CHECK:
     #[account(mut, seeds = [b"sol_vault"], bump = platform.bump_sol_vault)]
     sol_vault: AccountInfo<'info>,
-    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    #[account(
+        mut, seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
     platform: Account<'info, Platform>,
     #[account(mut, seeds = [b"fctr_mint"], bump = platform.bump_fctr_mint)]
     fctr_mint: Account<'info, Mint>,
@@ -31,8 +37,7 @@ pub struct SellFctrTokens<'info> {
 }
 
 pub fn sell_fctr_tokens(ctx: Context<SellFctrTokens>) -> Result<()> {
-    let lamports_to_get =
-        ctx.accounts.fctr_vault.amount / (101 * 10u64.pow((FCTR_DECIMALS - DECIMALS) as _));
+    let lamports_to_get = pricing::lamports_for_fctr(ctx.accounts.fctr_vault.amount);
     invoke_signed(
         &system_instruction::transfer(
             ctx.accounts.sol_vault.key,
@@ -57,11 +62,20 @@ pub fn sell_fctr_tokens(ctx: Context<SellFctrTokens>) -> Result<()> {
         signer,
     );
     ctx.accounts.user.user_fctr_amount = 0;
+    ctx.accounts.user_stats.fctr_sold += ctx.accounts.fctr_vault.amount;
+    ctx.accounts.user_stats.sol_received += lamports_to_get;
+    ctx.accounts.platform.total_sol_out += lamports_to_get;
     ctx.accounts.platform.fctr_token_total_amount -= ctx.accounts.fctr_vault.amount;
     token::burn(cpi_ctx, ctx.accounts.fctr_vault.amount)?;
 
     emit!(SellFctrTokensEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
         amount: ctx.accounts.fctr_vault.amount,
+        lamports: lamports_to_get,
+        fctr_total_amount: ctx.accounts.platform.fctr_token_total_amount,
     });
 
     Ok(())
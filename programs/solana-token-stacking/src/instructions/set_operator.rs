@@ -0,0 +1,29 @@
+use crate::{
+    events::{EventHeader, OperatorUpdatedEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    #[account(mut, seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(address = user.authority)]
+    authority: Signer<'info>,
+    #[account(seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+}
+
+pub fn set_operator(ctx: Context<SetOperator>, operator: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.user.operator = operator;
+
+    emit!(OperatorUpdatedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        operator,
+    });
+
+    Ok(())
+}
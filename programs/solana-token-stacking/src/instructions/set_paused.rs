@@ -0,0 +1,27 @@
+use crate::{
+    events::{EventHeader, PausedEvent},
+    state::Platform,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Account<'info, Platform>,
+    #[account(address = platform.authority)]
+    authority: Signer<'info>,
+}
+
+pub fn set_paused(ctx: Context<SetPaused>, is_paused: bool) -> Result<()> {
+    ctx.accounts.platform.is_paused = is_paused;
+
+    emit!(PausedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        is_paused,
+    });
+
+    Ok(())
+}
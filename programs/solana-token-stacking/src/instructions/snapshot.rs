@@ -0,0 +1,93 @@
+use crate::state::{Receipt, Snapshot, VotingWeight};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, SnapshotEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SnapshotVotingWeight<'info> {
+    #[account(seeds = [b"platform"], bump = platform.bump)]
+    platform: Account<'info, Platform>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"snapshot", platform.round_index.to_le_bytes().as_ref()],
+        bump,
+        space = 8 + Snapshot::SPACE,
+    )]
+    snapshot: Account<'info, Snapshot>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"voting_weight", platform.round_index.to_le_bytes().as_ref(), authority.key().as_ref()],
+        bump,
+        space = 8 + VotingWeight::SPACE,
+    )]
+    voting_weight: Account<'info, VotingWeight>,
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
+    receipt: Account<'info, Receipt>,
+    /// CHECK:
+    #[account(address = user.authority)]
+    authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    system_program: Program<'info, System>,
+}
+
+pub fn snapshot(ctx: Context<SnapshotVotingWeight>) -> Result<()> {
+    if ctx.accounts.receipt.authority != ctx.accounts.authority.key() {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    let round_ends = ctx.accounts.platform.round_start + ctx.accounts.platform.round_duration;
+    if !ctx.accounts.receipt.is_valid || ctx.accounts.receipt.round_ends != round_ends {
+        return err!(CustomErrors::NotStakedInRound);
+    }
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    if snapshot.stakers_count == 0 {
+        snapshot.bump = *ctx.bumps.get("snapshot").ok_or(CustomErrors::EmptyBump)?;
+        snapshot.round_index = ctx.accounts.platform.round_index;
+        snapshot.round_start = ctx.accounts.platform.round_start;
+    }
+    let weight = time_weighted_stake(&ctx.accounts.receipt, ctx.accounts.platform.round_duration);
+    snapshot.total_weight += weight;
+    snapshot.stakers_count += 1;
+
+    let voting_weight = &mut ctx.accounts.voting_weight;
+    voting_weight.bump = *ctx
+        .bumps
+        .get("voting_weight")
+        .ok_or(CustomErrors::EmptyBump)?;
+    voting_weight.round_index = ctx.accounts.platform.round_index;
+    voting_weight.weight = weight;
+    voting_weight.authority = ctx.accounts.authority.key();
+
+    emit!(SnapshotEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        weight,
+        total_weight: snapshot.total_weight,
+    });
+
+    Ok(())
+}
+
+/// The staker's own FCTR, counted for the share of the round it is staked for.
+///
+/// Only the own stake is locked until the round ends. Grantors can claim their grants back
+/// and grant or stake them again within the round, so granted FCTR would count twice.
+fn time_weighted_stake(receipt: &Receipt, round_duration: u64) -> u64 {
+    let granted: u64 = receipt.grantors.iter().map(|g| g.amount).sum();
+    let own_stake = receipt.amount_deposited.saturating_sub(granted);
+    if round_duration == 0 {
+        return own_stake;
+    }
+    (own_stake as u128 * receipt.stake_duration.min(round_duration) as u128
+        / round_duration as u128) as u64
+}
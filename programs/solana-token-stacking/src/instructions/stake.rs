@@ -1,8 +1,8 @@
 use crate::state::Receipt;
 use crate::{
     errors::CustomErrors,
-    events::StakeEvent,
-    state::{Platform, User},
+    events::{EventHeader, StakeEvent},
+    state::{Platform, User, UserStats},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token;
@@ -12,13 +12,22 @@ use anchor_spl::token::{Token, TokenAccount, Transfer};
 pub struct Stake<'info> {
     #[account(mut, seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
     receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
     #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
     user: Account<'info, User>,
     #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
     fctr_vault: Account<'info, TokenAccount>,
-    #[account(mut, address = user.authority)]
-    authority: Signer<'info>,
-    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    /// CHECK:
+    #[account(address = user.authority)]
+    authority: UncheckedAccount<'info>,
+    #[account(constraint = user.can_operate(&signer.key()) @ CustomErrors::UnauthorizedSigner)]
+    signer: Signer<'info>,
+    #[account(
+        mut, seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ CustomErrors::PlatformPaused,
+    )]
     platform: Account<'info, Platform>,
     #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
     platform_fctr_token_vault: Account<'info, TokenAccount>,
@@ -53,11 +62,20 @@ pub fn stake(ctx: Context<Stake>) -> Result<()> {
     token::transfer(cpi_ctx, ctx.accounts.user.user_fctr_amount)?;
 
     ctx.accounts.receipt.is_valid = true;
+    ctx.accounts.user_stats.rounds_participated += 1;
+    ctx.accounts.platform.active_stakers += 1;
     ctx.accounts.receipt.stake_ts = now;
     ctx.accounts.receipt.round_ends =
         ctx.accounts.platform.round_start + ctx.accounts.platform.round_duration;
     ctx.accounts.receipt.stake_duration =
         ctx.accounts.platform.round_start + ctx.accounts.platform.round_duration - now;
+    // Grants received while not staked are credited as part of the full stake.
+    let stake_duration = ctx.accounts.receipt.stake_duration;
+    ctx.accounts
+        .receipt
+        .grantors
+        .iter_mut()
+        .for_each(|g| g.grant_duration = stake_duration);
     ctx.accounts.receipt.amount_deposited = ctx.accounts.user.user_fctr_amount
         + ctx
             .accounts
@@ -66,8 +84,17 @@ pub fn stake(ctx: Context<Stake>) -> Result<()> {
             .iter()
             .fold(0, |sum, g| sum + g.amount);
 
+    ctx.accounts.platform.total_fctr_staked += ctx.accounts.receipt.amount_deposited;
+    ctx.accounts.platform.total_fctr_pending -=
+        ctx.accounts.receipt.amount_deposited - ctx.accounts.user.user_fctr_amount;
+
     emit!(StakeEvent {
-        amount: ctx.accounts.fctr_vault.amount
+        header: EventHeader::new(ctx.accounts.signer.key(), ctx.accounts.platform.round_index)?,
+        owner: ctx.accounts.authority.key(),
+        amount: ctx.accounts.user.user_fctr_amount,
+        granted_amount: ctx.accounts.receipt.amount_deposited - ctx.accounts.user.user_fctr_amount,
+        amount_deposited: ctx.accounts.receipt.amount_deposited,
+        round_ends: ctx.accounts.receipt.round_ends,
     });
 
     Ok(())
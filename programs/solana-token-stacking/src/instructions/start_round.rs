@@ -1,4 +1,8 @@
-use crate::{errors::CustomErrors, events::RoundStartEvent, state::Platform};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, RoundStartEvent},
+    state::Platform,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -17,10 +21,19 @@ pub fn start_round(ctx: Context<StartRound>, is_final: bool) -> Result<()> {
     } else if ctx.accounts.platform.is_final {
         return err!(CustomErrors::StackingFinished);
     }
+    ctx.accounts.platform.round_index += 1;
     ctx.accounts.platform.round_start = now;
     ctx.accounts.platform.is_final = is_final;
 
-    emit!(RoundStartEvent { is_final });
+    emit!(RoundStartEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        is_final,
+        round_start: now,
+        round_ends: now + ctx.accounts.platform.round_duration,
+    });
 
     Ok(())
 }
@@ -1,7 +1,8 @@
-use crate::state::Receipt;
+use crate::rewards::{self, Grant, RewardSplit};
+use crate::state::{GrantHistory, Receipt, Reputation, UserStats};
 use crate::{
     errors::CustomErrors,
-    events::UnstakeEvent,
+    events::{EventHeader, GrantorReturn, UnstakeEvent},
     state::{Platform, User},
 };
 use anchor_lang::prelude::*;
@@ -13,14 +14,23 @@ use itertools::Itertools;
 pub struct Unstake<'info> {
     #[account(mut, seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
     receipt: Account<'info, Receipt>,
+    #[account(mut, seeds = [b"grant_history", authority.key().as_ref()], bump = grant_history.bump)]
+    grant_history: Box<Account<'info, GrantHistory>>,
+    #[account(mut, seeds = [b"reputation", authority.key().as_ref()], bump = reputation.bump)]
+    reputation: Box<Account<'info, Reputation>>,
+    #[account(mut, seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
     #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
     user: Account<'info, User>,
     #[account(mut, seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
     fctr_vault: Account<'info, TokenAccount>,
     #[account(mut, seeds = [b"bcdev_vault", authority.key().as_ref()], bump = user.bump_bcdev_vault)]
     bcdev_vault: Account<'info, TokenAccount>,
-    #[account(mut, address = user.authority)]
-    authority: Signer<'info>,
+    /// CHECK:
+    #[account(address = user.authority)]
+    authority: UncheckedAccount<'info>,
+    #[account(constraint = user.can_operate(&signer.key()) @ CustomErrors::UnauthorizedSigner)]
+    signer: Signer<'info>,
     #[account(mut, seeds = [b"platform"], bump = platform.bump)]
     platform: Box<Account<'info, Platform>>,
     #[account(mut, seeds = [b"fctr_token_vault"], bump = platform.bump_fctr_token_vault)]
@@ -31,6 +41,8 @@ pub struct Unstake<'info> {
     token_program: Program<'info, Token>,
 }
 
+/// Each grantor of the receipt is passed as four remaining accounts, in any order of grantors:
+/// its `User` account, FCTR vault, BCDEV vault and statistics account.
 pub fn unstake<'info>(ctx: Context<'_, '_, '_, 'info, Unstake<'info>>) -> Result<()> {
     let now: u64 = Clock::get()?.unix_timestamp as _;
     if ctx.accounts.receipt.authority != ctx.accounts.authority.key() {
@@ -45,80 +57,119 @@ pub fn unstake<'info>(ctx: Context<'_, '_, '_, 'info, Unstake<'info>>) -> Result
         return err!(CustomErrors::RoundStillGoing);
     }
 
-    if ctx.remaining_accounts.len() / 3 != ctx.accounts.receipt.grantors.len() {
+    if ctx.remaining_accounts.len() != 4 * ctx.accounts.receipt.grantors.len() {
         return err!(CustomErrors::InvalidGrantorsList);
     }
 
+    // Every grantor of the receipt has to be passed exactly once.
+    let mut unmatched = ctx.accounts.receipt.grantors.clone();
     let grantors_accounts = ctx
         .remaining_accounts
-        .chunks_exact(3)
-        .take(12)
-        .map(|pair| {
-            let grantor_user = Account::<User>::try_from(&pair[0])?;
-            let grantor_fctr_vault = Account::<TokenAccount>::try_from(&pair[1])?;
-            let grantor_bcdev_vault = Account::<TokenAccount>::try_from(&pair[2])?;
-            let grantor_from_account = ctx
-                .accounts
-                .receipt
-                .grantors
+        .chunks_exact(4)
+        .map(|accounts| {
+            let grantor_user = Account::<User>::try_from(&accounts[0])?;
+            let position = unmatched
                 .iter()
-                .find(|g| g.grantor == grantor_user.key())
+                .position(|g| g.grantor == grantor_user.key())
                 .ok_or(CustomErrors::InvalidGrantorsList)?;
-            let grantor = GrantorsToReward {
+            let grantor_from_account = unmatched.swap_remove(position);
+            let grantor_fctr_vault = Account::<TokenAccount>::try_from(&accounts[1])?;
+            let grantor_bcdev_vault = Account::<TokenAccount>::try_from(&accounts[2])?;
+            if grantor_fctr_vault.key()
+                != vault_address(b"fctr_vault", &grantor_user, grantor_user.bump_fctr_vault)?
+                || grantor_bcdev_vault.key()
+                    != vault_address(b"bcdev_vault", &grantor_user, grantor_user.bump_bcdev_vault)?
+            {
+                return err!(CustomErrors::InvalidGrantorsList);
+            }
+            let grantor_stats = Account::<UserStats>::try_from(&accounts[3])?;
+            if grantor_stats.authority != grantor_user.authority {
+                return err!(CustomErrors::InvalidGrantorsList);
+            }
+            Ok(GrantorsToReward {
                 user: grantor_user,
                 fctr_vault: grantor_fctr_vault,
                 bcdev_vault: grantor_bcdev_vault,
+                stats: grantor_stats,
                 grant_amount: grantor_from_account.amount,
                 grant_duration: grantor_from_account.grant_duration,
-            };
-            ctx.accounts
-                .receipt
-                .grantors
-                .retain(|g| g.grantor != grantor.user.authority);
-            Ok(grantor)
+                grantor_share_bps: grantor_from_account.grantor_share_bps,
+            })
         })
         .collect::<Result<Vec<GrantorsToReward>>>()?
         .into_iter()
         .sorted_by(|a, b| a.grant_duration.cmp(&b.grant_duration))
         .collect::<Vec<GrantorsToReward>>();
 
-    if grantors_accounts.len() != ctx.accounts.receipt.grantors.len() {
+    if !unmatched.is_empty() {
         return err!(CustomErrors::InvalidGrantorsList);
     }
 
-    let total_reward = calculate_reward(&grantors_accounts, &ctx);
+    let grants = grantors_accounts
+        .iter()
+        .map(|g| Grant {
+            amount: g.grant_amount,
+            duration: g.grant_duration,
+            grantor_share_bps: g.grantor_share_bps,
+        })
+        .collect::<Vec<Grant>>();
+    let total_reward = rewards::total_reward(
+        ctx.accounts.receipt.amount_deposited,
+        ctx.accounts.receipt.apr,
+        ctx.accounts.receipt.stake_duration,
+        &grants,
+        ctx.accounts.platform.round_duration,
+    );
+    let split = rewards::split_reward(total_reward, &grants);
     let total_granted_fctr = return_fctr(&grantors_accounts, &ctx)?;
-    mint_reward(&grantors_accounts, &ctx, total_reward, total_granted_fctr)?;
+    let grantor_returns = mint_reward(&grantors_accounts, &ctx, &split)?;
+    let staker_reward = split.staker_reward;
+    let principal_returned = ctx.accounts.receipt.amount_deposited - total_granted_fctr;
+    ctx.accounts.user_stats.bcdev_earned += staker_reward;
+    // Grantor stats come from the remaining accounts, so they have to be written back by hand.
+    grantors_accounts
+        .into_iter()
+        .zip(grantor_returns.iter())
+        .try_for_each(|(mut grantor, grantor_return)| {
+            grantor.stats.bcdev_earned += grantor_return.reward;
+            grantor.stats.exit(&crate::ID)
+        })?;
 
-    ctx.accounts.platform.bcdev_token_total_amount += total_reward;
+    // Shares are rounded down, so only what was actually minted is added to the totals.
+    let minted_reward = split.total();
+    ctx.accounts.platform.bcdev_token_total_amount += minted_reward;
+    ctx.accounts.platform.total_bcdev_rewards += minted_reward;
+    ctx.accounts.platform.total_fctr_staked -= ctx.accounts.receipt.amount_deposited;
+    ctx.accounts.platform.total_fctr_granted -= total_granted_fctr;
+    ctx.accounts.platform.active_stakers -= 1;
+    let on_time =
+        now <= ctx.accounts.receipt.round_ends + ctx.accounts.platform.reclaim_grace_period;
+    ctx.accounts.reputation.record_unstake(on_time);
     ctx.accounts.receipt.is_valid = false;
     ctx.accounts.receipt.grantors.clear();
     ctx.accounts
-        .receipt
-        .grantors_history
-        .retain(|g| (g.grant_ts + 30 * ctx.accounts.platform.round_duration) < now);
+        .grant_history
+        .retain(|g| (g.grant_ts + 30 * ctx.accounts.platform.round_duration) >= now);
 
-    emit!(UnstakeEvent {});
+    emit!(UnstakeEvent {
+        header: EventHeader::new(ctx.accounts.signer.key(), ctx.accounts.platform.round_index)?,
+        owner: ctx.accounts.authority.key(),
+        principal_returned,
+        total_reward,
+        staker_reward,
+        grantor_returns,
+        fctr_balance: ctx.accounts.fctr_vault.amount + principal_returned,
+        bcdev_balance: ctx.accounts.bcdev_vault.amount + staker_reward,
+        on_time,
+    });
 
     Ok(())
 }
 
-fn calculate_reward<'info>(
-    grantors_to_reward: &Vec<GrantorsToReward>,
-    ctx: &Context<'_, '_, '_, 'info, Unstake<'info>>,
-) -> u64 {
-    let apr = ctx.accounts.receipt.apr + grantors_to_reward.len() as f64;
-    let reward =
-        grantors_to_reward
-            .iter()
-            .fold(AprReward::apply(apr, 0), |reward_amount, grantor| {
-                let reward_percentage = apr
-                    * (grantor.grant_duration as f64 / ctx.accounts.platform.round_duration as f64);
-                let g_reward = (ctx.accounts.receipt.amount_deposited as f64 * reward_percentage)
-                    .round() as u64;
-                AprReward::apply(reward_amount.apr - 0.02, reward_amount.reward + g_reward)
-            });
-    reward.reward + (ctx.accounts.receipt.amount_deposited as f64 * apr) as u64
+/// Vault of a grantor, derived from the bump stored in its `User` account.
+fn vault_address(seed: &[u8], grantor: &User, bump: u8) -> Result<Pubkey> {
+    Pubkey::create_program_address(&[seed, grantor.authority.as_ref(), &[bump]], &crate::ID)
+        .map_err(|_| error!(CustomErrors::InvalidGrantorsList))
 }
 
 fn return_fctr<'info>(
@@ -159,30 +210,24 @@ fn return_fctr<'info>(
 }
 
 fn mint_reward<'info>(
-    grantors_to_reward: &Vec<GrantorsToReward<'info>>,
+    grantors_to_reward: &[GrantorsToReward<'info>],
     ctx: &Context<'_, '_, '_, 'info, Unstake<'info>>,
-    total_reward: u64,
-    total_granted_fctr: u64,
-) -> Result<()> {
+    split: &RewardSplit,
+) -> Result<Vec<GrantorReturn>> {
     let signer: &[&[&[u8]]] = &[&[b"platform", &[ctx.accounts.platform.bump]]];
-    if grantors_to_reward.len() != 0 {
-        let staker_reward = total_reward / 2;
-        let grantors_reward = total_reward - staker_reward;
-        mint_bcdev(staker_reward, &ctx.accounts.bcdev_vault, ctx, signer)?;
-
-        grantors_to_reward
-            .iter()
-            .map(|g| {
-                let share = (grantors_reward as f64 * g.grant_amount as f64
-                    / total_granted_fctr as f64) as u64;
-                mint_bcdev(share, &g.bcdev_vault, ctx, signer)
+    mint_bcdev(split.staker_reward, &ctx.accounts.bcdev_vault, ctx, signer)?;
+    grantors_to_reward
+        .iter()
+        .zip(split.grantor_rewards.iter())
+        .map(|(g, reward)| {
+            mint_bcdev(*reward, &g.bcdev_vault, ctx, signer)?;
+            Ok(GrantorReturn {
+                grantor: g.user.key(),
+                amount: g.grant_amount,
+                reward: *reward,
             })
-            .collect::<Result<Vec<_>>>()?;
-
-        Ok(())
-    } else {
-        mint_bcdev(total_reward, &ctx.accounts.bcdev_vault, ctx, signer)
-    }
+        })
+        .collect()
 }
 
 fn mint_bcdev<'info>(
@@ -207,17 +252,8 @@ struct GrantorsToReward<'a> {
     pub user: Account<'a, User>,
     pub fctr_vault: Account<'a, TokenAccount>,
     pub bcdev_vault: Account<'a, TokenAccount>,
+    pub stats: Account<'a, UserStats>,
     pub grant_amount: u64,
     pub grant_duration: u64,
-}
-
-struct AprReward {
-    pub apr: f64,
-    pub reward: u64,
-}
-
-impl AprReward {
-    pub fn apply(apr: f64, reward: u64) -> AprReward {
-        AprReward { apr, reward }
-    }
+    pub grantor_share_bps: u16,
 }
@@ -0,0 +1,49 @@
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, GrantLimitsUpdatedEvent},
+    state::{Platform, RewardProjection},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateGrantLimits<'info> {
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Account<'info, Platform>,
+    #[account(address = platform.authority)]
+    authority: Signer<'info>,
+}
+
+pub fn update_grant_limits(
+    ctx: Context<UpdateGrantLimits>,
+    max_active_grantors: u32,
+    max_grantors_history: u32,
+    min_grant_ratio: f64,
+    max_grant_ratio: f64,
+) -> Result<()> {
+    // `project_reward` returns every active grantor.
+    if max_active_grantors == 0
+        || max_active_grantors as usize > RewardProjection::MAX_GRANTOR_RETURNS
+        || max_grantors_history < max_active_grantors
+        || min_grant_ratio <= 0.0
+        || max_grant_ratio < min_grant_ratio
+    {
+        return err!(CustomErrors::InvalidGrantLimits);
+    }
+    ctx.accounts.platform.max_active_grantors = max_active_grantors;
+    ctx.accounts.platform.max_grantors_history = max_grantors_history;
+    ctx.accounts.platform.min_grant_ratio = min_grant_ratio;
+    ctx.accounts.platform.max_grant_ratio = max_grant_ratio;
+
+    emit!(GrantLimitsUpdatedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        max_active_grantors,
+        max_grantors_history,
+        min_grant_ratio,
+        max_grant_ratio,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::{
+    events::{EventHeader, GrantOfferTtlUpdatedEvent},
+    state::Platform,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateGrantOfferTtl<'info> {
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Account<'info, Platform>,
+    #[account(address = platform.authority)]
+    authority: Signer<'info>,
+}
+
+pub fn update_grant_offer_ttl(ctx: Context<UpdateGrantOfferTtl>, ttl_rounds: u64) -> Result<()> {
+    ctx.accounts.platform.grant_offer_ttl_rounds = ttl_rounds;
+
+    emit!(GrantOfferTtlUpdatedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        ttl_rounds,
+    });
+
+    Ok(())
+}
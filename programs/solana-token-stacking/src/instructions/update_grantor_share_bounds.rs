@@ -0,0 +1,38 @@
+use crate::state::BPS_DENOMINATOR;
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, GrantorShareBoundsUpdatedEvent},
+    state::Platform,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateGrantorShareBounds<'info> {
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Account<'info, Platform>,
+    #[account(address = platform.authority)]
+    authority: Signer<'info>,
+}
+
+pub fn update_grantor_share_bounds(
+    ctx: Context<UpdateGrantorShareBounds>,
+    min_grantor_share_bps: u16,
+    max_grantor_share_bps: u16,
+) -> Result<()> {
+    if max_grantor_share_bps < min_grantor_share_bps || BPS_DENOMINATOR < max_grantor_share_bps {
+        return err!(CustomErrors::InvalidGrantorShare);
+    }
+    ctx.accounts.platform.min_grantor_share_bps = min_grantor_share_bps;
+    ctx.accounts.platform.max_grantor_share_bps = max_grantor_share_bps;
+
+    emit!(GrantorShareBoundsUpdatedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        min_grantor_share_bps,
+        max_grantor_share_bps,
+    });
+
+    Ok(())
+}
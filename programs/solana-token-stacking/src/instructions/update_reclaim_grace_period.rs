@@ -0,0 +1,30 @@
+use crate::{
+    events::{EventHeader, ReclaimGracePeriodUpdatedEvent},
+    state::Platform,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateReclaimGracePeriod<'info> {
+    #[account(mut, seeds = [b"platform"], bump = platform.bump)]
+    platform: Account<'info, Platform>,
+    #[account(address = platform.authority)]
+    authority: Signer<'info>,
+}
+
+pub fn update_reclaim_grace_period(
+    ctx: Context<UpdateReclaimGracePeriod>,
+    grace_period: u64,
+) -> Result<()> {
+    ctx.accounts.platform.reclaim_grace_period = grace_period;
+
+    emit!(ReclaimGracePeriodUpdatedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        grace_period,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::state::{TrustEntry, TrustList, TrustListMode, MAX_TRUST_LIST_ENTRIES};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, TrustListUpdatedEvent},
+    state::{Platform, User},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateTrustList<'info> {
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(mut, seeds = [b"trust_list", authority.key().as_ref()], bump = trust_list.bump)]
+    trust_list: Account<'info, TrustList>,
+    #[account(address = user.authority)]
+    authority: Signer<'info>,
+    #[account(seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+}
+
+pub fn update_trust_list(
+    ctx: Context<UpdateTrustList>,
+    mode: TrustListMode,
+    entries: Vec<TrustEntry>,
+) -> Result<()> {
+    if entries.len() > MAX_TRUST_LIST_ENTRIES {
+        return err!(CustomErrors::TrustListFull);
+    }
+    ctx.accounts.trust_list.mode = mode;
+    ctx.accounts.trust_list.entries = entries;
+
+    emit!(TrustListUpdatedEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        mode,
+        entries_count: ctx.accounts.trust_list.entries.len() as u32,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::instructions::project_reward::project_receipt_reward;
+use crate::state::{Receipt, Reputation, UserStats, UserSummary};
+use crate::{
+    errors::CustomErrors,
+    state::{Platform, User},
+};
+use anchor_lang::{prelude::*, solana_program::program::set_return_data};
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct GetUserSummary<'info> {
+    #[account(seeds = [b"user", authority.key().as_ref()], bump = user.bump)]
+    user: Account<'info, User>,
+    #[account(seeds = [b"receipt", authority.key().as_ref()], bump = user.bump_receipt)]
+    receipt: Account<'info, Receipt>,
+    #[account(seeds = [b"fctr_vault", authority.key().as_ref()], bump = user.bump_fctr_vault)]
+    fctr_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [b"bcdev_vault", authority.key().as_ref()], bump = user.bump_bcdev_vault)]
+    bcdev_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [b"reputation", authority.key().as_ref()], bump = reputation.bump)]
+    reputation: Box<Account<'info, Reputation>>,
+    #[account(seeds = [b"user_stats", authority.key().as_ref()], bump = user_stats.bump)]
+    user_stats: Box<Account<'info, UserStats>>,
+    /// CHECK:
+    authority: UncheckedAccount<'info>,
+    #[account(seeds = [b"platform"], bump = platform.bump)]
+    platform: Box<Account<'info, Platform>>,
+}
+
+pub fn user_summary(ctx: Context<GetUserSummary>) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let receipt = &ctx.accounts.receipt;
+    if receipt.authority != user.authority {
+        return err!(CustomErrors::InvalidReceiptAuthority);
+    }
+    let mut projection = project_receipt_reward(receipt, ctx.accounts.platform.round_duration);
+    // Return data is capped, the grantors that don't fit are listed by `project_reward`.
+    projection
+        .grantor_returns
+        .truncate(UserSummary::MAX_GRANTOR_RETURNS);
+    let summary = UserSummary {
+        authority: user.authority,
+        operator: user.operator,
+        grant_program: user.grant_program,
+        fctr_balance: ctx.accounts.fctr_vault.amount,
+        bcdev_balance: ctx.accounts.bcdev_vault.amount,
+        user_fctr_amount: user.user_fctr_amount,
+        next_position_id: user.next_position_id,
+        is_staked: receipt.is_valid,
+        apr: receipt.apr,
+        grantors_count: receipt.grantors.len() as u32,
+        next_round_grantors_count: receipt.next_round_grantors.len() as u32,
+        projection,
+        rounds_participated: ctx.accounts.user_stats.rounds_participated,
+        bcdev_earned: ctx.accounts.user_stats.bcdev_earned,
+        on_time_unstakes: ctx.accounts.reputation.on_time_unstakes,
+        late_unstakes: ctx.accounts.reputation.late_unstakes,
+        missed_unstakes: ctx.accounts.reputation.missed_unstakes,
+    };
+    set_return_data(&summary.try_to_vec()?);
+
+    Ok(())
+}
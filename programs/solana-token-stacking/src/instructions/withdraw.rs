@@ -1,4 +1,8 @@
-use crate::{errors::CustomErrors, events::WithdrawEvent, state::Platform};
+use crate::{
+    errors::CustomErrors,
+    events::{EventHeader, WithdrawEvent},
+    state::Platform,
+};
 use anchor_lang::{
     prelude::*,
     solana_program::{program::invoke_signed, system_instruction},
@@ -28,11 +32,12 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         return err!(CustomErrors::WithdrawConditions);
     }
 
+    let lamports = ctx.accounts.sol_vault.lamports() - Rent::get()?.minimum_balance(0);
     invoke_signed(
         &system_instruction::transfer(
             ctx.accounts.sol_vault.key,
             ctx.accounts.authority.key,
-            ctx.accounts.sol_vault.lamports() - Rent::get()?.minimum_balance(0),
+            lamports,
         ),
         &[
             ctx.accounts.sol_vault.to_account_info(),
@@ -41,7 +46,15 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         &[&[b"sol_vault", &[ctx.accounts.platform.bump_sol_vault]]],
     )?;
 
-    emit!(WithdrawEvent {});
+    ctx.accounts.platform.total_sol_out += lamports;
+
+    emit!(WithdrawEvent {
+        header: EventHeader::new(
+            ctx.accounts.authority.key(),
+            ctx.accounts.platform.round_index
+        )?,
+        lamports,
+    });
 
     Ok(())
 }
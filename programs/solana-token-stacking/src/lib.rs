@@ -1,15 +1,18 @@
 use crate::instructions::*;
+use crate::state::{TrustEntry, TrustListMode};
 use anchor_lang::prelude::*;
 
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod pricing;
+pub mod rewards;
 pub mod state;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
-const FCTR_DECIMALS: u8 = 12;
-const BCDEV_DECIMALS: u8 = 18;
+pub const FCTR_DECIMALS: u8 = 12;
+pub const BCDEV_DECIMALS: u8 = 18;
 
 #[program]
 pub mod solana_token_stacking {
@@ -30,10 +33,84 @@ pub mod solana_token_stacking {
         register_user::register_user(ctx, participate_in_grant_program)
     }
 
+    pub fn update_trust_list(
+        ctx: Context<UpdateTrustList>,
+        mode: TrustListMode,
+        entries: Vec<TrustEntry>,
+    ) -> Result<()> {
+        update_trust_list::update_trust_list(ctx, mode, entries)
+    }
+
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Option<Pubkey>) -> Result<()> {
+        set_operator::set_operator(ctx, operator)
+    }
+
+    pub fn audit_platform(ctx: Context<AuditPlatform>, auto_pause: bool) -> Result<()> {
+        audit_platform::audit_platform(ctx, auto_pause)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, is_paused: bool) -> Result<()> {
+        set_paused::set_paused(ctx, is_paused)
+    }
+
     pub fn start_round(ctx: Context<StartRound>, is_final: bool) -> Result<()> {
         start_round::start_round(ctx, is_final)
     }
 
+    pub fn migrate_platform(ctx: Context<MigratePlatform>) -> Result<()> {
+        migrate_platform::migrate_platform(ctx)
+    }
+
+    pub fn migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+        migrate_user::migrate_user(ctx)
+    }
+
+    pub fn update_grant_limits(
+        ctx: Context<UpdateGrantLimits>,
+        max_active_grantors: u32,
+        max_grantors_history: u32,
+        min_grant_ratio: f64,
+        max_grant_ratio: f64,
+    ) -> Result<()> {
+        update_grant_limits::update_grant_limits(
+            ctx,
+            max_active_grantors,
+            max_grantors_history,
+            min_grant_ratio,
+            max_grant_ratio,
+        )
+    }
+
+    pub fn resize_receipt(ctx: Context<ResizeReceipt>) -> Result<()> {
+        resize_receipt::resize_receipt(ctx)
+    }
+
+    pub fn update_reclaim_grace_period(
+        ctx: Context<UpdateReclaimGracePeriod>,
+        grace_period: u64,
+    ) -> Result<()> {
+        update_reclaim_grace_period::update_reclaim_grace_period(ctx, grace_period)
+    }
+
+    pub fn update_grant_offer_ttl(
+        ctx: Context<UpdateGrantOfferTtl>,
+        ttl_rounds: u64,
+    ) -> Result<()> {
+        update_grant_offer_ttl::update_grant_offer_ttl(ctx, ttl_rounds)
+    }
+
+    pub fn update_grantor_share_bounds(
+        ctx: Context<UpdateGrantorShareBounds>,
+        min_grantor_share_bps: u16,
+        max_grantor_share_bps: u16,
+    ) -> Result<()> {
+        update_grantor_share_bounds::update_grantor_share_bounds(
+            ctx,
+            min_grantor_share_bps,
+            max_grantor_share_bps,
+        )
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         withdraw::withdraw(ctx)
     }
@@ -62,11 +139,75 @@ pub mod solana_token_stacking {
         unstake::unstake(ctx)
     }
 
-    pub fn grant_tokens(ctx: Context<GrantTokens>, amount: u64) -> Result<()> {
-        grant_tokens::grant_tokens(ctx, amount)
+    pub fn grant_tokens(
+        ctx: Context<GrantTokens>,
+        amount: u64,
+        grantor_share_bps: u16,
+    ) -> Result<()> {
+        grant_tokens::grant_tokens(ctx, amount, grantor_share_bps)
+    }
+
+    pub fn offer_grant(
+        ctx: Context<OfferGrant>,
+        amount: u64,
+        grantor_share_bps: u16,
+    ) -> Result<()> {
+        offer_grant::offer_grant(ctx, amount, grantor_share_bps)
+    }
+
+    pub fn accept_grant(ctx: Context<AcceptGrant>) -> Result<()> {
+        accept_grant::accept_grant(ctx)
+    }
+
+    pub fn reject_grant(ctx: Context<RejectGrant>) -> Result<()> {
+        reject_grant::reject_grant(ctx)
+    }
+
+    pub fn revoke_grant(ctx: Context<RevokeGrant>) -> Result<()> {
+        revoke_grant::revoke_grant(ctx)
+    }
+
+    pub fn reclaim_grant(ctx: Context<ReclaimGrant>) -> Result<()> {
+        reclaim_grant::reclaim_grant(ctx)
     }
 
     pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
         claim_tokens::claim_tokens(ctx)
     }
+
+    pub fn open_position(ctx: Context<OpenPosition>, amount: u64, lock_rounds: u8) -> Result<()> {
+        open_position::open_position(ctx, amount, lock_rounds)
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        close_position::close_position(ctx)
+    }
+
+    pub fn snapshot(ctx: Context<SnapshotVotingWeight>) -> Result<()> {
+        snapshot::snapshot(ctx)
+    }
+
+    pub fn prove_voting_weight(ctx: Context<ProveVotingWeight>, round_index: u64) -> Result<()> {
+        prove_voting_weight::prove_voting_weight(ctx, round_index)
+    }
+
+    pub fn quote_buy(ctx: Context<QuoteTokens>, lamports: u64) -> Result<()> {
+        quote_tokens::quote_buy(ctx, lamports)
+    }
+
+    pub fn quote_sell_fctr(ctx: Context<QuoteTokens>, amount: u64) -> Result<()> {
+        quote_tokens::quote_sell_fctr(ctx, amount)
+    }
+
+    pub fn quote_sell_bcdev(ctx: Context<QuoteTokens>, amount: u64) -> Result<()> {
+        quote_tokens::quote_sell_bcdev(ctx, amount)
+    }
+
+    pub fn project_reward(ctx: Context<ProjectReward>) -> Result<()> {
+        project_reward::project_reward(ctx)
+    }
+
+    pub fn user_summary(ctx: Context<GetUserSummary>) -> Result<()> {
+        user_summary::user_summary(ctx)
+    }
 }
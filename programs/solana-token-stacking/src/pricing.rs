@@ -0,0 +1,25 @@
+//! Exchange rates between SOL and the platform tokens.
+use crate::{BCDEV_DECIMALS, FCTR_DECIMALS};
+use anchor_spl::token::spl_token::native_mint::DECIMALS;
+
+/// FCTR minted for `lamports` paid into the SOL vault, `None` if it doesn't fit into a `u64`.
+pub fn fctr_for_lamports(lamports: u64) -> Option<u64> {
+    lamports
+        .checked_mul(109)?
+        .checked_mul(10u64.pow((FCTR_DECIMALS - DECIMALS) as _))
+}
+
+/// Lamports paid out of the SOL vault for `amount` FCTR burned.
+pub fn lamports_for_fctr(amount: u64) -> u64 {
+    amount / (101 * 10u64.pow((FCTR_DECIMALS - DECIMALS) as _))
+}
+
+/// Lamports paid out of the SOL vault for `amount` BCDEV burned.
+pub fn lamports_for_bcdev(amount: u64) -> u64 {
+    amount / (11 * 10u64.pow((BCDEV_DECIMALS - DECIMALS) as _))
+}
+
+/// Lamports the SOL vault owes if every FCTR and BCDEV token were sold back.
+pub fn sol_liability(fctr_supply: u64, bcdev_supply: u64) -> u64 {
+    lamports_for_fctr(fctr_supply) + lamports_for_bcdev(bcdev_supply)
+}
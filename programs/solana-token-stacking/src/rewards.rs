@@ -0,0 +1,203 @@
+//! BCDEV rewards of a receipt and how they are split between the staker and the grantors.
+use crate::state::{GrantorRecord, BPS_DENOMINATOR};
+use itertools::Itertools;
+
+/// FCTR granted into a stake, as far as the reward is concerned.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grant {
+    pub amount: u64,
+    /// How long the granted FCTR was staked.
+    pub duration: u64,
+    pub grantor_share_bps: u16,
+}
+
+impl From<&GrantorRecord> for Grant {
+    fn from(record: &GrantorRecord) -> Self {
+        Grant {
+            amount: record.amount,
+            duration: record.grant_duration,
+            grantor_share_bps: record.grantor_share_bps,
+        }
+    }
+}
+
+/// A reward split between the staker and the grantors, in the order the grants were given.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RewardSplit {
+    pub staker_reward: u64,
+    pub grantor_rewards: Vec<u64>,
+}
+
+impl RewardSplit {
+    /// Shares are rounded down, so this can be slightly less than the split reward.
+    pub fn total(&self) -> u64 {
+        self.staker_reward + self.grantor_rewards.iter().sum::<u64>()
+    }
+}
+
+/// Reward of `deposited` FCTR staked for `stake_duration`, `grants` included.
+///
+/// Each grant raises the APR by one and earns a bonus for the time it was staked, with the
+/// bonus APR dropping by 0.02 from the shortest grant to the longest one. Rewards that don't
+/// fit in a `u64` are capped, they could never be minted anyway.
+pub fn total_reward(
+    deposited: u64,
+    apr: f64,
+    stake_duration: u64,
+    grants: &[Grant],
+    round_duration: u64,
+) -> u64 {
+    let apr = apr + grants.len() as f64;
+    let (_, bonus) = grants.iter().map(|grant| grant.duration).sorted().fold(
+        (apr, 0u64),
+        |(grant_apr, bonus), duration| {
+            let reward_percentage = grant_apr * (duration as f64 / round_duration as f64);
+            let grant_bonus = (deposited as f64 * reward_percentage).round() as u64;
+            (grant_apr - 0.02, bonus.saturating_add(grant_bonus))
+        },
+    );
+    let base = (time_weighted_deposit(deposited, stake_duration, grants) as f64 * apr) as u64;
+    bonus.saturating_add(base)
+}
+
+/// APR of a grantor after granting `amount` out of `balance` FCTR.
+///
+/// The grantor's own stake shrinks by the grant, so the APR grows to keep their reward whole.
+/// Granting the whole balance leaves nothing to earn on, and the APR as it was.
+pub fn grantor_apr(apr: f64, balance: u64, amount: u64) -> f64 {
+    if amount >= balance {
+        return apr;
+    }
+    apr * balance as f64 / (balance - amount) as f64
+}
+
+/// APR of a grantor after revoking a grant of `amount` out of the `balance` FCTR they held
+/// when granting, which undoes [`grantor_apr`] whatever their balance is by now.
+pub fn revoked_grantor_apr(apr: f64, balance: u64, amount: u64) -> f64 {
+    if amount >= balance {
+        return apr;
+    }
+    apr * (balance - amount) as f64 / balance as f64
+}
+
+/// Granted tokens only count for the part of the stake they were actually staked for.
+///
+/// The grants are part of the deposit, so their amounts never add up to more than `deposited`.
+pub fn time_weighted_deposit(deposited: u64, stake_duration: u64, grants: &[Grant]) -> u64 {
+    if stake_duration == 0 {
+        return deposited;
+    }
+    grants.iter().fold(deposited, |deposit, grant| {
+        let staked_part = mul_div(
+            grant.amount,
+            grant.duration.min(stake_duration) as u128,
+            stake_duration as u128,
+        );
+        deposit - (grant.amount - staked_part)
+    })
+}
+
+/// Splits `total_reward` the way `unstake` mints it.
+///
+/// Without grants the staker keeps everything. Otherwise the reward is split by granted amount
+/// and each grantor gets their negotiated share of their part.
+pub fn split_reward(total_reward: u64, grants: &[Grant]) -> RewardSplit {
+    if grants.is_empty() {
+        return RewardSplit {
+            staker_reward: total_reward,
+            grantor_rewards: Vec::new(),
+        };
+    }
+    let total_granted = total_granted(grants);
+    RewardSplit {
+        staker_reward: staker_reward_share(
+            total_reward,
+            grants.iter().map(|g| (g.amount, g.grantor_share_bps)),
+            total_granted,
+        ),
+        grantor_rewards: grants
+            .iter()
+            .map(|g| {
+                grantor_reward_share(total_reward, g.amount, g.grantor_share_bps, total_granted)
+            })
+            .collect(),
+    }
+}
+
+/// Reward of a grant reclaimed from a stake that was never unstaked.
+///
+/// Only the grant itself counts, as if it had been staked on its own for as long as it was
+/// granted, so grantors reclaiming from the same receipt don't depend on each other or on the
+/// order they reclaim in.
+pub fn reclaimed_grant_reward(apr: f64, grant: Grant, round_duration: u64) -> u64 {
+    let total_reward = total_reward(grant.amount, apr, grant.duration, &[grant], round_duration);
+    grantor_reward_share(
+        total_reward,
+        grant.amount,
+        grant.grantor_share_bps,
+        grant.amount,
+    )
+}
+
+/// The part of `total_reward` the grantors negotiated for themselves all together.
+pub fn grantor_pool(total_reward: u64, grants: &[Grant]) -> u64 {
+    let grantor_weight = grants.iter().fold(0u128, |sum, g| {
+        sum + g.amount as u128 * g.grantor_share_bps as u128
+    });
+    share(total_reward, grantor_weight, total_granted(grants))
+}
+
+/// Each grantor gets their negotiated share of the reward earned by their part of the granted
+/// FCTR.
+pub fn grantor_reward_share(
+    total_reward: u64,
+    grant_amount: u64,
+    grantor_share_bps: u16,
+    total_granted: u64,
+) -> u64 {
+    share(
+        total_reward,
+        grant_amount as u128 * grantor_share_bps as u128,
+        total_granted,
+    )
+}
+
+/// The staker keeps whatever each grantor didn't negotiate for themselves.
+pub fn staker_reward_share(
+    total_reward: u64,
+    grants: impl Iterator<Item = (u64, u16)>,
+    total_granted: u64,
+) -> u64 {
+    let staker_weight = grants.fold(0u128, |sum, (amount, grantor_share_bps)| {
+        sum + amount as u128 * (BPS_DENOMINATOR - grantor_share_bps) as u128
+    });
+    share(total_reward, staker_weight, total_granted)
+}
+
+fn total_granted(grants: &[Grant]) -> u64 {
+    grants.iter().map(|g| g.amount).sum()
+}
+
+/// `total_reward * weight / (total_granted * BPS_DENOMINATOR)`, where the weight is never more
+/// than the denominator.
+fn share(total_reward: u64, weight: u128, total_granted: u64) -> u64 {
+    if total_granted == 0 {
+        return 0;
+    }
+    mul_div(
+        total_reward,
+        weight,
+        total_granted as u128 * BPS_DENOMINATOR as u128,
+    )
+}
+
+/// `value * numerator / denominator` rounded down, for `numerator <= denominator < 2^95`.
+///
+/// `value` is split in halves of 32 bits so no intermediate product overflows a `u128`.
+fn mul_div(value: u64, numerator: u128, denominator: u128) -> u64 {
+    let high = (value >> 32) as u128 * numerator;
+    let low = (value & u32::MAX as u64) as u128 * numerator;
+    let quotient = (high / denominator) << 32;
+    let rest = ((high % denominator) << 32) + low;
+    (quotient + rest / denominator) as u64
+}
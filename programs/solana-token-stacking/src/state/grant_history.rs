@@ -0,0 +1,64 @@
+use crate::state::GrantorHistoryRecord;
+use anchor_lang::prelude::*;
+
+/// Grants a user received lately, kept as a fixed-size ring buffer so the grant cooldown
+/// never has to look past its capacity. Once full, the oldest record is overwritten.
+#[account]
+#[derive(Default)]
+pub struct GrantHistory {
+    pub bump: u8,
+    pub head: u32,
+    pub len: u32,
+    pub records: Vec<GrantorHistoryRecord>,
+    pub authority: Pubkey,
+}
+
+impl GrantHistory {
+    pub fn space(capacity: usize) -> usize {
+        1 + 4 + 4 + (4 + capacity * GrantorHistoryRecord::SPACE) + 32
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Records from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &GrantorHistoryRecord> {
+        let capacity = self.capacity().max(1);
+        let start = (self.head as usize + capacity - self.len as usize) % capacity;
+        (0..self.len as usize).map(move |i| &self.records[(start + i) % capacity])
+    }
+
+    pub fn contains(&self, grantor: &Pubkey) -> bool {
+        self.iter().any(|r| r.grantor == *grantor)
+    }
+
+    pub fn push(&mut self, record: GrantorHistoryRecord) {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            return;
+        }
+        self.records[self.head as usize] = record;
+        self.head = ((self.head as usize + 1) % capacity) as u32;
+        self.len = (self.len as usize + 1).min(capacity) as u32;
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(&GrantorHistoryRecord) -> bool) {
+        let kept = self.iter().copied().filter(|r| f(r)).collect();
+        self.rebuild(kept, self.capacity());
+    }
+
+    /// Changes the capacity of the buffer keeping the newest records.
+    pub fn resize(&mut self, capacity: usize) {
+        let records = self.iter().copied().collect::<Vec<_>>();
+        let skip = records.len().saturating_sub(capacity);
+        self.rebuild(records.into_iter().skip(skip).collect(), capacity);
+    }
+
+    fn rebuild(&mut self, mut records: Vec<GrantorHistoryRecord>, capacity: usize) {
+        self.len = records.len() as u32;
+        self.head = (records.len() % capacity.max(1)) as u32;
+        records.resize(capacity, GrantorHistoryRecord::default());
+        self.records = records;
+    }
+}
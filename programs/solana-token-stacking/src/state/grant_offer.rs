@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct GrantOffer {
+    pub bump: u8,
+    pub amount: u64,
+    pub grantor_share_bps: u16,
+    pub created_round: u64,
+    pub expires_round: u64,
+    pub grantor: Pubkey,
+    pub confidant: Pubkey,
+}
+
+impl GrantOffer {
+    pub const SPACE: usize = 1 + 8 + 2 + 8 + 8 + 32 + 32;
+}
@@ -1,12 +1,17 @@
 use anchor_lang::prelude::*;
 
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default)]
 pub struct GrantorRecord {
     pub amount: u64,
     pub grant_duration: u64,
+    pub grantor_share_bps: u16,
     pub grantor: Pubkey,
+    /// FCTR the grantor held before granting, which `revoke_grant` needs to undo the APR bump.
+    pub grantor_balance: u64,
 }
 
 impl GrantorRecord {
-    pub const SPACE: usize = 8 + 8 + 32;
+    pub const SPACE: usize = 8 + 8 + 2 + 32 + 8;
 }
@@ -0,0 +1,88 @@
+//! Layouts of the accounts as the first version of the program left them, read once by
+//! `migrate_platform` and `migrate_user`. Accounts of that version are told apart by their size.
+//!
+//! `User` and `Platform` only had fields appended since, so grown accounts of the first version
+//! read as the current ones with the new fields zeroed. `Receipt` moved its grant history out
+//! and its grants gained the grantor's share, so it has to be converted.
+use crate::state::{GrantorHistoryRecord, GrantorRecord, Receipt};
+use anchor_lang::prelude::*;
+
+/// The first version split the reward of a grant in halves.
+pub const LEGACY_GRANTOR_SHARE_BPS: u16 = 5_000;
+pub const LEGACY_MAX_ACTIVE_GRANTORS: usize = 4;
+pub const LEGACY_MAX_GRANTORS_HISTORY: usize = 100;
+
+pub const LEGACY_USER_SPACE: usize = 1 + 1 + 1 + 1 + 1 + 8 + 32;
+pub const LEGACY_PLATFORM_SPACE: usize = 1 + 1 + 1 + 1 + 1 + 8 + 1 + 8 + 8 + 8 + 8 + 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default)]
+pub struct LegacyGrantorRecord {
+    pub amount: u64,
+    pub grant_duration: u64,
+    pub grantor: Pubkey,
+}
+
+impl LegacyGrantorRecord {
+    pub const SPACE: usize = 8 + 8 + 32;
+}
+
+impl From<LegacyGrantorRecord> for GrantorRecord {
+    fn from(record: LegacyGrantorRecord) -> Self {
+        GrantorRecord {
+            amount: record.amount,
+            grant_duration: record.grant_duration,
+            grantor_share_bps: LEGACY_GRANTOR_SHARE_BPS,
+            grantor: record.grantor,
+            // Not kept by the first version, revoking such a grant leaves the APR as it is.
+            grantor_balance: 0,
+        }
+    }
+}
+
+/// Receipt of the first version, without its discriminator, which hasn't changed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct LegacyReceipt {
+    pub is_valid: bool,
+    pub stake_duration: u64,
+    pub stake_ts: u64,
+    pub round_ends: u64,
+    pub amount_deposited: u64,
+    pub apr: f64,
+    pub grantors: Vec<LegacyGrantorRecord>,
+    pub grantors_history: Vec<GrantorHistoryRecord>,
+    pub next_round_grantors: Vec<LegacyGrantorRecord>,
+    pub authority: Pubkey,
+}
+
+impl LegacyReceipt {
+    pub const SPACE: usize = 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + (4 + LEGACY_MAX_ACTIVE_GRANTORS * LegacyGrantorRecord::SPACE)
+        + (4 + LEGACY_MAX_ACTIVE_GRANTORS * LegacyGrantorRecord::SPACE)
+        + (4 + LEGACY_MAX_GRANTORS_HISTORY * GrantorHistoryRecord::SPACE);
+
+    /// The current receipt and the grant history it held, oldest record first.
+    pub fn migrate(self) -> (Receipt, Vec<GrantorHistoryRecord>) {
+        let receipt = Receipt {
+            is_valid: self.is_valid,
+            stake_duration: self.stake_duration,
+            stake_ts: self.stake_ts,
+            round_ends: self.round_ends,
+            amount_deposited: self.amount_deposited,
+            apr: self.apr,
+            grantors: self.grantors.into_iter().map(Into::into).collect(),
+            next_round_grantors: self
+                .next_round_grantors
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            authority: self.authority,
+        };
+        (receipt, self.grantors_history)
+    }
+}
@@ -1,11 +1,31 @@
+pub use grant_history::*;
+pub use grant_offer::*;
 pub use grantor_history_record::*;
 pub use grantor_record::*;
+pub use legacy::*;
 pub use platform::*;
+pub use position::*;
 pub use receipt::*;
+pub use reputation::*;
+pub use snapshot::*;
+pub use trust_list::*;
 pub use user::*;
+pub use user_stats::*;
+pub use views::*;
+pub use voting_weight::*;
 
+pub mod grant_history;
+pub mod grant_offer;
 pub mod grantor_history_record;
 pub mod grantor_record;
+pub mod legacy;
 pub mod platform;
+pub mod position;
 pub mod receipt;
+pub mod reputation;
+pub mod snapshot;
+pub mod trust_list;
 pub mod user;
+pub mod user_stats;
+pub mod views;
+pub mod voting_weight;
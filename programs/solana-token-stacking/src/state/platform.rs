@@ -1,5 +1,14 @@
+use crate::state::{GrantHistory, Receipt};
 use anchor_lang::prelude::*;
 
+pub const DEFAULT_MAX_ACTIVE_GRANTORS: u32 = 4;
+pub const DEFAULT_MAX_GRANTORS_HISTORY: u32 = 100;
+pub const DEFAULT_MIN_GRANT_RATIO: f64 = 0.5;
+pub const DEFAULT_MAX_GRANT_RATIO: f64 = 2.0;
+pub const DEFAULT_GRANT_OFFER_TTL_ROUNDS: u64 = 2;
+pub const DEFAULT_MIN_GRANTOR_SHARE_BPS: u16 = 1_000;
+pub const DEFAULT_MAX_GRANTOR_SHARE_BPS: u16 = 9_000;
+
 #[account]
 pub struct Platform {
     pub bump: u8,
@@ -14,8 +23,62 @@ pub struct Platform {
     pub bcdev_token_total_amount: u64,
     pub registration_price: u64,
     pub authority: Pubkey,
+    pub round_index: u64,
+    pub max_active_grantors: u32,
+    pub max_grantors_history: u32,
+    pub min_grant_ratio: f64,
+    pub max_grant_ratio: f64,
+    pub reclaim_grace_period: u64,
+    pub grant_offer_ttl_rounds: u64,
+    pub min_grantor_share_bps: u16,
+    pub max_grantor_share_bps: u16,
+    pub total_fctr_staked: u64,
+    pub active_stakers: u64,
+    pub total_fctr_granted: u64,
+    pub total_bcdev_rewards: u64,
+    pub total_sol_in: u64,
+    pub total_sol_out: u64,
+    pub total_fctr_pending: u64,
+    pub total_fctr_offered: u64,
+    pub is_paused: bool,
 }
 
 impl Platform {
-    pub const SPACE: usize = 1 + 1 + 1 + 1 + 1 + 8 + 1 + 8 + 8 + 8 + 8 + 32;
+    pub const SPACE: usize = 1
+        + 1
+        + 1
+        + 1
+        + 1
+        + 8
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 8
+        + 4
+        + 4
+        + 8
+        + 8
+        + 8
+        + 8
+        + 2
+        + 2
+        + 8 * 8
+        + 1;
+
+    pub fn is_valid_grantor_share(&self, grantor_share_bps: u16) -> bool {
+        (self.min_grantor_share_bps..=self.max_grantor_share_bps).contains(&grantor_share_bps)
+    }
+
+    /// Space a receipt needs to hold the currently configured number of grantors.
+    pub fn receipt_space(&self) -> usize {
+        Receipt::space(self.max_active_grantors as usize)
+    }
+
+    /// Space a grant history needs to hold the currently configured number of records.
+    pub fn grant_history_space(&self) -> usize {
+        GrantHistory::space(self.max_grantors_history as usize)
+    }
 }
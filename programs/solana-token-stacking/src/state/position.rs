@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_LOCK_ROUNDS: u8 = 3;
+
+#[account]
+#[derive(Default)]
+pub struct Position {
+    pub bump: u8,
+    pub id: u64,
+    pub lock_rounds: u8,
+    pub stake_ts: u64,
+    pub stake_duration: u64,
+    pub round_ends: u64,
+    pub unlock_ts: u64,
+    pub amount_deposited: u64,
+    pub apr: f64,
+    pub authority: Pubkey,
+}
+
+impl Position {
+    pub const SPACE: usize = 1 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 32;
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+/// Track record of a user as a confidant, used by grantors to decide whom to trust.
+#[account]
+#[derive(Default)]
+pub struct Reputation {
+    pub bump: u8,
+    pub grants_received: u64,
+    pub fctr_entrusted: u64,
+    pub on_time_unstakes: u64,
+    pub late_unstakes: u64,
+    pub missed_unstakes: u64,
+    pub last_missed_round_ends: u64,
+    pub authority: Pubkey,
+}
+
+impl Reputation {
+    pub const SPACE: usize = 1 + 8 + 8 + 8 + 8 + 8 + 8 + 32;
+
+    pub fn record_grant(&mut self, amount: u64) {
+        self.grants_received += 1;
+        self.fctr_entrusted += amount;
+    }
+
+    /// Undoes `record_grant` for a revoked grant.
+    pub fn revoke_grant(&mut self, amount: u64) {
+        self.grants_received = self.grants_received.saturating_sub(1);
+        self.fctr_entrusted = self.fctr_entrusted.saturating_sub(amount);
+    }
+
+    /// Unstaking after the reclaim grace period is late.
+    pub fn record_unstake(&mut self, on_time: bool) {
+        if on_time {
+            self.on_time_unstakes += 1;
+        } else {
+            self.late_unstakes += 1;
+        }
+    }
+
+    /// Every grantor may reclaim his grant from the same missed round, it's counted once.
+    pub fn record_missed_unstake(&mut self, round_ends: u64) {
+        if self.last_missed_round_ends != round_ends {
+            self.missed_unstakes += 1;
+            self.last_missed_round_ends = round_ends;
+        }
+    }
+}
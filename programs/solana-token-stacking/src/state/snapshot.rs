@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct Snapshot {
+    pub bump: u8,
+    pub round_index: u64,
+    pub round_start: u64,
+    pub total_weight: u64,
+    pub stakers_count: u64,
+}
+
+impl Snapshot {
+    pub const SPACE: usize = 1 + 8 + 8 + 8 + 8;
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_TRUST_LIST_ENTRIES: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TrustListMode {
+    #[default]
+    Open,
+    AllowListOnly,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default)]
+pub struct TrustEntry {
+    pub user: Pubkey,
+    pub allowed: bool,
+}
+
+impl TrustEntry {
+    pub const SPACE: usize = 32 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct TrustList {
+    pub bump: u8,
+    pub mode: TrustListMode,
+    pub entries: Vec<TrustEntry>,
+    pub authority: Pubkey,
+}
+
+impl TrustList {
+    pub const SPACE: usize = 1 + 1 + (4 + MAX_TRUST_LIST_ENTRIES * TrustEntry::SPACE) + 32;
+
+    /// Blocked users are always rejected, others pass unless only allowed users are accepted.
+    pub fn permits(&self, user: &Pubkey) -> bool {
+        match self.entries.iter().find(|e| e.user == *user) {
+            Some(entry) => entry.allowed,
+            None => self.mode == TrustListMode::Open,
+        }
+    }
+
+    /// Whether `user` is explicitly allowed, regardless of the mode.
+    pub fn allows(&self, user: &Pubkey) -> bool {
+        self.entries.iter().any(|e| e.user == *user && e.allowed)
+    }
+}
@@ -9,8 +9,15 @@ pub struct User {
     pub grant_program: bool,
     pub user_fctr_amount: u64,
     pub authority: Pubkey,
+    pub next_position_id: u64,
+    pub operator: Option<Pubkey>,
 }
 
 impl User {
-    pub const SPACE: usize = 1 + 1 + 1 + 1 + 1 + 8 + 32;
+    pub const SPACE: usize = 1 + 1 + 1 + 1 + 1 + 8 + 32 + 8 + (1 + 32);
+
+    /// Staking actions may be signed either by the owner or by the delegated operator.
+    pub fn can_operate(&self, signer: &Pubkey) -> bool {
+        self.authority == *signer || self.operator == Some(*signer)
+    }
 }
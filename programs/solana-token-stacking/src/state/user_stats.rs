@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// Lifetime totals of a user, kept so they don't have to be recomputed from the history.
+#[account]
+#[derive(Default)]
+pub struct UserStats {
+    pub bump: u8,
+    pub fctr_bought: u64,
+    pub fctr_sold: u64,
+    pub bcdev_earned: u64,
+    pub bcdev_sold: u64,
+    pub rounds_participated: u64,
+    pub grants_given: u64,
+    pub fctr_granted: u64,
+    pub grants_received: u64,
+    pub fctr_received: u64,
+    pub fctr_claimed: u64,
+    pub sol_paid: u64,
+    pub sol_received: u64,
+    pub authority: Pubkey,
+}
+
+impl UserStats {
+    pub const SPACE: usize = 1 + 8 * 12 + 32;
+}
@@ -0,0 +1,72 @@
+use crate::events::GrantorReturn;
+use anchor_lang::{prelude::*, solana_program::program::MAX_RETURN_DATA};
+
+/// What a trade would pay out at the current rates.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default)]
+pub struct TradeQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// The reward a receipt would earn if it were unstaked with its current grants.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct RewardProjection {
+    pub amount_deposited: u64,
+    pub round_ends: u64,
+    pub total_reward: u64,
+    pub staker_reward: u64,
+    pub grantor_returns: Vec<GrantorReturn>,
+}
+
+impl RewardProjection {
+    /// Size of the projection without its grantor returns.
+    const FIXED_SPACE: usize = 8 + 8 + 8 + 8 + 4;
+    /// Grantor returns that fit into the return data, which bounds the active grantors.
+    pub const MAX_GRANTOR_RETURNS: usize =
+        (MAX_RETURN_DATA - Self::FIXED_SPACE) / GrantorReturn::SPACE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct UserSummary {
+    pub authority: Pubkey,
+    pub operator: Option<Pubkey>,
+    pub grant_program: bool,
+    pub fctr_balance: u64,
+    pub bcdev_balance: u64,
+    pub user_fctr_amount: u64,
+    pub next_position_id: u64,
+    pub is_staked: bool,
+    pub apr: f64,
+    pub grantors_count: u32,
+    pub next_round_grantors_count: u32,
+    pub projection: RewardProjection,
+    pub rounds_participated: u64,
+    pub bcdev_earned: u64,
+    pub on_time_unstakes: u64,
+    pub late_unstakes: u64,
+    pub missed_unstakes: u64,
+}
+
+impl UserSummary {
+    /// Size of the summary without the grantor returns of its projection.
+    const FIXED_SPACE: usize = 32
+        + (1 + 32)
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
+        + 4
+        + 4
+        + RewardProjection::FIXED_SPACE
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8;
+    /// Grantor returns that fit into the return data along with the rest of the summary.
+    pub const MAX_GRANTOR_RETURNS: usize =
+        (MAX_RETURN_DATA - Self::FIXED_SPACE) / GrantorReturn::SPACE;
+}
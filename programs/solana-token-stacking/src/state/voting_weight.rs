@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct VotingWeight {
+    pub bump: u8,
+    pub round_index: u64,
+    pub weight: u64,
+    pub authority: Pubkey,
+}
+
+impl VotingWeight {
+    pub const SPACE: usize = 1 + 8 + 8 + 32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default)]
+pub struct VotingWeightProof {
+    pub round_index: u64,
+    pub weight: u64,
+    pub total_weight: u64,
+    pub authority: Pubkey,
+}
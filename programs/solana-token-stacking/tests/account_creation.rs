@@ -0,0 +1,217 @@
+//! Instructions that create or grow accounts, which only run on the compiled program:
+//! `cargo build-bpf` and then `cargo test --features test-bpf`.
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_token_stacking::errors::CustomErrors;
+use solana_token_stacking::state::{
+    GrantHistory, GrantOffer, Position, Receipt, Snapshot, User, VotingWeight,
+    DEFAULT_MAX_ACTIVE_GRANTORS, DEFAULT_MAX_GRANTORS_HISTORY, DEFAULT_MAX_GRANT_RATIO,
+    DEFAULT_MIN_GRANT_RATIO, MAX_LOCK_ROUNDS,
+};
+use solana_token_stacking_client::{instructions, pda};
+
+#[tokio::test]
+async fn initializes_the_platform() {
+    let mut env = Genesis::new().start_without_platform().await;
+    let authority = env.platform_authority.pubkey();
+
+    env.send_as_authority(instructions::initialize(
+        &authority,
+        ROUND_DURATION,
+        REGISTRATION_PRICE,
+    ))
+    .await
+    .unwrap();
+
+    let platform = env.platform().await;
+    let expected = initialized_platform(authority);
+    assert_eq!(platform.authority, authority);
+    assert_eq!(platform.round_duration, ROUND_DURATION);
+    assert_eq!(platform.registration_price, REGISTRATION_PRICE);
+    assert_eq!(platform.bump, expected.bump);
+    assert_eq!(platform.max_active_grantors, expected.max_active_grantors);
+    assert_eq!(env.token_amount(pda::fctr_token_vault()).await, 0);
+}
+
+#[tokio::test]
+async fn registers_users() {
+    let mut genesis = Genesis::new();
+    let wallet = Keypair::new();
+    genesis
+        .program_test
+        .add_account(wallet.pubkey(), system_account(USER_LAMPORTS));
+    let mut env = genesis.start().await;
+    let authority = wallet.pubkey();
+
+    env.send(instructions::register_user(&authority, true), &[&wallet])
+        .await
+        .unwrap();
+
+    let user: User = env.account(pda::user(&authority)).await;
+    assert_eq!(user.authority, authority);
+    assert!(user.grant_program);
+    let receipt: Receipt = env.account(pda::receipt(&authority)).await;
+    assert_eq!(receipt.authority, authority);
+    let history: GrantHistory = env.account(pda::grant_history(&authority)).await;
+    assert_eq!(history.capacity(), DEFAULT_MAX_GRANTORS_HISTORY as usize);
+    assert_eq!(env.token_amount(pda::fctr_vault(&authority)).await, 0);
+    assert_eq!(env.token_amount(pda::bcdev_vault(&authority)).await, 0);
+    assert_eq!(env.platform().await.total_sol_in, REGISTRATION_PRICE);
+}
+
+#[tokio::test]
+async fn offers_grants() {
+    let mut env = Genesis::new().with_users(2).start().await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    let minted = env.buy(1, 1_000_000).await;
+
+    let result = env
+        .send_as_user(
+            1,
+            instructions::offer_grant(&grantor, &confidant, minted, 5_000),
+        )
+        .await;
+    assert_custom_error(result, CustomErrors::TokenGrantError);
+
+    env.send_as_user(
+        1,
+        instructions::offer_grant(&grantor, &confidant, minted / 2, 5_000),
+    )
+    .await
+    .unwrap();
+
+    let offer: GrantOffer = env.account(pda::grant_offer(&grantor, &confidant)).await;
+    assert_eq!(offer.amount, minted / 2);
+    assert_eq!(offer.grantor, grantor);
+    assert_eq!(offer.confidant, confidant);
+    assert_eq!(env.fctr_balance(1).await, minted - minted / 2);
+    assert_eq!(env.platform().await.total_fctr_offered, minted / 2);
+}
+
+#[tokio::test]
+async fn opens_positions() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    let minted = env.buy(0, 1_000_000).await;
+
+    let result = env
+        .send_as_user(0, instructions::open_position(&user, 0, minted, 1))
+        .await;
+    assert_custom_error(result, CustomErrors::NoActiveRound);
+
+    env.start_round().await;
+    let result = env
+        .send_as_user(
+            0,
+            instructions::open_position(&user, 0, minted, MAX_LOCK_ROUNDS + 1),
+        )
+        .await;
+    assert_custom_error(result, CustomErrors::InvalidLockTier);
+    let result = env
+        .send_as_user(0, instructions::open_position(&user, 0, minted + 1, 1))
+        .await;
+    assert_custom_error(result, CustomErrors::InvalidPositionAmount);
+
+    env.send_as_user(0, instructions::open_position(&user, 0, minted / 2, 1))
+        .await
+        .unwrap();
+
+    let position: Position = env.account(pda::position(&user, 0)).await;
+    assert_eq!(position.amount_deposited, minted / 2);
+    assert_eq!(position.unlock_ts, START_TIME as u64 + 2 * ROUND_DURATION);
+    let user_account: User = env.account(pda::user(&user)).await;
+    assert_eq!(user_account.next_position_id, 1);
+    assert_eq!(user_account.user_fctr_amount, minted - minted / 2);
+}
+
+#[tokio::test]
+async fn snapshots_voting_weight() {
+    let mut env = Genesis::new().with_users(3).start().await;
+    let first = env.user(0);
+    let second = env.user(1);
+    let grantor = env.user(2);
+    let minted = env.buy(0, 1_000_000).await;
+    env.buy(1, 2_000_000).await;
+    env.buy(2, 1_000_000).await;
+    env.trust(0, &[2]).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+
+    let result = env
+        .send_as_user(1, instructions::snapshot(&second, &second, 1))
+        .await;
+    assert_custom_error(result, CustomErrors::NotStakedInRound);
+
+    // Halfway through the round the second user stakes and the first one receives a grant.
+    env.warp(ROUND_DURATION as i64 / 2);
+    env.stake(1).await.unwrap();
+    let granted = minted / 2;
+    env.send_as_user(
+        2,
+        instructions::grant_tokens(&grantor, &first, granted, 5_000),
+    )
+    .await
+    .unwrap();
+    env.send_as_user(0, instructions::snapshot(&first, &first, 1))
+        .await
+        .unwrap();
+    env.send_as_user(0, instructions::snapshot(&first, &second, 1))
+        .await
+        .unwrap();
+
+    // The grant doesn't count, since its grantor could claim it back before the round ends,
+    // and the second user only counts for the half of the round they are staked for.
+    let first_weight = minted;
+    let second_weight = minted;
+    let snapshot: Snapshot = env.account(pda::snapshot(1)).await;
+    assert_eq!(snapshot.stakers_count, 2);
+    assert_eq!(snapshot.total_weight, first_weight + second_weight);
+    let weight: VotingWeight = env.account(pda::voting_weight(1, &first)).await;
+    assert_eq!(weight.weight, first_weight);
+    let weight: VotingWeight = env.account(pda::voting_weight(1, &second)).await;
+    assert_eq!(weight.weight, second_weight);
+    env.send(instructions::prove_voting_weight(&second, 1), &[])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn resizes_receipts_to_new_grant_limits() {
+    let mut env = Genesis::new().with_users(2).start().await;
+    let authority = env.platform_authority.pubkey();
+    let user = env.user(0);
+    let minted = env.buy(0, 1_000_000).await;
+    env.buy(1, 1_000_000).await;
+    env.trust(0, &[1]).await;
+    env.send_as_authority(instructions::update_grant_limits(
+        &authority,
+        DEFAULT_MAX_ACTIVE_GRANTORS + 2,
+        DEFAULT_MAX_GRANTORS_HISTORY + 20,
+        DEFAULT_MIN_GRANT_RATIO,
+        DEFAULT_MAX_GRANT_RATIO,
+    ))
+    .await
+    .unwrap();
+
+    env.send_as_user(1, instructions::resize_receipt(&env.user(1), &user))
+        .await
+        .unwrap();
+
+    let history: GrantHistory = env.account(pda::grant_history(&user)).await;
+    assert_eq!(
+        history.capacity(),
+        DEFAULT_MAX_GRANTORS_HISTORY as usize + 20
+    );
+    let grantor = env.user(1);
+    env.send_as_user(
+        1,
+        instructions::grant_tokens(&grantor, &user, minted / 2, 5_000),
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,227 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_token_stacking::errors::CustomErrors;
+use solana_token_stacking::state::RewardProjection;
+use solana_token_stacking_client::{instructions, pda};
+
+#[tokio::test]
+async fn starts_rounds() {
+    let mut env = Genesis::new().start().await;
+    let authority = env.platform_authority.pubkey();
+
+    env.start_round().await;
+    let platform = env.platform().await;
+    assert_eq!(platform.round_index, 1);
+    assert_eq!(platform.round_start, START_TIME as u64);
+
+    let result = env
+        .send_as_authority(instructions::start_round(&authority, true))
+        .await;
+    assert_custom_error(result, CustomErrors::RoundAlreadyStarted);
+
+    env.warp(ROUND_DURATION as i64);
+    env.send_as_authority(instructions::start_round(&authority, true))
+        .await
+        .unwrap();
+    let platform = env.platform().await;
+    assert_eq!(platform.round_index, 2);
+    assert_eq!(platform.round_start, START_TIME as u64 + ROUND_DURATION);
+    assert!(platform.is_final);
+
+    env.warp(ROUND_DURATION as i64);
+    let result = env
+        .send_as_authority(instructions::start_round(&authority, false))
+        .await;
+    assert_custom_error(result, CustomErrors::StackingFinished);
+}
+
+#[tokio::test]
+async fn only_the_platform_authority_manages_the_platform() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+
+    let result = env
+        .send_as_user(0, instructions::start_round(&user, false))
+        .await;
+    assert_anchor_error(result, ErrorCode::ConstraintAddress);
+    let result = env
+        .send_as_user(0, instructions::set_paused(&user, true))
+        .await;
+    assert_anchor_error(result, ErrorCode::ConstraintAddress);
+    let result = env.send_as_user(0, instructions::withdraw(&user)).await;
+    assert_anchor_error(result, ErrorCode::ConstraintAddress);
+}
+
+#[tokio::test]
+async fn pauses_and_resumes() {
+    let mut env = Genesis::new().start().await;
+    let authority = env.platform_authority.pubkey();
+
+    env.send_as_authority(instructions::set_paused(&authority, true))
+        .await
+        .unwrap();
+    assert!(env.platform().await.is_paused);
+
+    env.send_as_authority(instructions::set_paused(&authority, false))
+        .await
+        .unwrap();
+    assert!(!env.platform().await.is_paused);
+}
+
+#[tokio::test]
+async fn audit_keeps_a_consistent_platform_running() {
+    let mut env = Genesis::new().with_users(2).start().await;
+    let authority = env.platform_authority.pubkey();
+    env.buy(0, 1_000_000).await;
+    env.buy(1, 1_000_000).await;
+    // FCTR sells back above its buying price, so the vault needs liquidity to cover it.
+    env.send_as_authority(instructions::add_liquidity(&authority, 1_000_000))
+        .await
+        .unwrap();
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    // Tokens sent straight to the vault don't make the platform inconsistent.
+    let vault_amount = env.token_amount(pda::fctr_token_vault()).await;
+    env.set_token_amount(pda::fctr_token_vault(), vault_amount + 1)
+        .await;
+    let auditor = Keypair::new();
+
+    env.send(
+        instructions::audit_platform(&auditor.pubkey(), true),
+        &[&auditor],
+    )
+    .await
+    .unwrap();
+
+    assert!(!env.platform().await.is_paused);
+}
+
+#[tokio::test]
+async fn audit_pauses_an_inconsistent_platform() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    env.buy(0, 1_000_000).await;
+    env.update_platform(|platform| platform.fctr_token_total_amount += 1)
+        .await;
+    let auditor = Keypair::new();
+
+    env.send(
+        instructions::audit_platform(&auditor.pubkey(), false),
+        &[&auditor],
+    )
+    .await
+    .unwrap();
+    assert!(!env.platform().await.is_paused);
+
+    env.send(
+        instructions::audit_platform(&auditor.pubkey(), true),
+        &[&auditor],
+    )
+    .await
+    .unwrap();
+    assert!(env.platform().await.is_paused);
+}
+
+#[tokio::test]
+async fn updates_grant_limits() {
+    let mut env = Genesis::new().start().await;
+    let authority = env.platform_authority.pubkey();
+
+    env.send_as_authority(instructions::update_grant_limits(
+        &authority, 6, 120, 0.25, 4.0,
+    ))
+    .await
+    .unwrap();
+
+    let platform = env.platform().await;
+    assert_eq!(platform.max_active_grantors, 6);
+    assert_eq!(platform.max_grantors_history, 120);
+    assert_eq!(platform.min_grant_ratio, 0.25);
+    assert_eq!(platform.max_grant_ratio, 4.0);
+}
+
+#[tokio::test]
+async fn rejects_invalid_grant_limits() {
+    let mut env = Genesis::new().start().await;
+    let authority = env.platform_authority.pubkey();
+
+    for (max_active, history, min_ratio, max_ratio) in [
+        (0, 100, 0.5, 2.0),
+        (
+            RewardProjection::MAX_GRANTOR_RETURNS as u32 + 1,
+            100,
+            0.5,
+            2.0,
+        ),
+        (10, 5, 0.5, 2.0),
+        (4, 100, 0.0, 2.0),
+        (4, 100, 2.0, 0.5),
+    ] {
+        let result = env
+            .send_as_authority(instructions::update_grant_limits(
+                &authority, max_active, history, min_ratio, max_ratio,
+            ))
+            .await;
+        assert_custom_error(result, CustomErrors::InvalidGrantLimits);
+    }
+}
+
+#[tokio::test]
+async fn updates_grant_timings() {
+    let mut env = Genesis::new().start().await;
+    let authority = env.platform_authority.pubkey();
+
+    env.send_as_authority(instructions::update_reclaim_grace_period(&authority, 500))
+        .await
+        .unwrap();
+    env.send_as_authority(instructions::update_grant_offer_ttl(&authority, 5))
+        .await
+        .unwrap();
+
+    let platform = env.platform().await;
+    assert_eq!(platform.reclaim_grace_period, 500);
+    assert_eq!(platform.grant_offer_ttl_rounds, 5);
+}
+
+#[tokio::test]
+async fn updates_grantor_share_bounds() {
+    let mut env = Genesis::new().start().await;
+    let authority = env.platform_authority.pubkey();
+
+    env.send_as_authority(instructions::update_grantor_share_bounds(
+        &authority, 2_000, 5_000,
+    ))
+    .await
+    .unwrap();
+    let platform = env.platform().await;
+    assert_eq!(platform.min_grantor_share_bps, 2_000);
+    assert_eq!(platform.max_grantor_share_bps, 5_000);
+
+    let result = env
+        .send_as_authority(instructions::update_grantor_share_bounds(
+            &authority, 5_000, 2_000,
+        ))
+        .await;
+    assert_custom_error(result, CustomErrors::InvalidGrantorShare);
+    let result = env
+        .send_as_authority(instructions::update_grantor_share_bounds(
+            &authority, 0, 10_001,
+        ))
+        .await;
+    assert_custom_error(result, CustomErrors::InvalidGrantorShare);
+}
+
+#[tokio::test]
+async fn keeps_receipts_that_fit_the_grant_limits() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+
+    let result = env
+        .send_as_user(0, instructions::resize_receipt(&user, &user))
+        .await;
+
+    assert_custom_error(result, CustomErrors::ReceiptAlreadySized);
+    assert!(env.exists(pda::receipt(&user)).await);
+}
@@ -0,0 +1,144 @@
+//! Runs the program as a builtin.
+//!
+//! The builtin CPI stub of `solana-program-test` looks up every key of the invoked
+//! instruction, the invoked program included, in the account infos it is given. The SPL token
+//! helpers of Anchor don't pass the token program, which a real validator doesn't need, so the
+//! stub is wrapped to add a copy of the program account from the accounts of the outer
+//! instruction. Builtins can't resize accounts, which is why the instructions that create or
+//! grow accounts are only tested with `test-bpf`.
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use std::cell::RefCell;
+use std::sync::Once;
+
+/// An executable account the program was given, as the CPI stub needs it.
+struct ProgramAccount {
+    key: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    owner: Pubkey,
+    rent_epoch: u64,
+}
+
+thread_local! {
+    /// Programs among the accounts of the instruction processed on this thread.
+    static PROGRAMS: RefCell<Vec<ProgramAccount>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    static INSTALL: Once = Once::new();
+    // The bank has installed the stubs of `solana-program-test` by the time anything runs.
+    INSTALL.call_once(|| {
+        let stubs = set_syscall_stubs(Box::new(CpiStubs(None)));
+        set_syscall_stubs(Box::new(CpiStubs(Some(stubs))));
+    });
+
+    let programs = accounts
+        .iter()
+        .filter(|account| account.executable)
+        .map(|account| ProgramAccount {
+            key: *account.key,
+            lamports: account.lamports(),
+            data: account.data.borrow().to_vec(),
+            owner: *account.owner,
+            rent_epoch: account.rent_epoch,
+        })
+        .collect();
+    PROGRAMS.with(|cell| *cell.borrow_mut() = programs);
+    solana_token_stacking::entry(program_id, accounts, instruction_data)
+}
+
+struct CpiStubs(Option<Box<dyn SyscallStubs>>);
+
+impl CpiStubs {
+    fn inner(&self) -> &dyn SyscallStubs {
+        self.0.as_deref().expect("syscall stubs are not installed")
+    }
+}
+
+impl SyscallStubs for CpiStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner().sol_log(message)
+    }
+
+    fn sol_log_compute_units(&self) {
+        self.inner().sol_log_compute_units()
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let mut account_infos = account_infos.to_vec();
+        if !account_infos
+            .iter()
+            .any(|info| info.key == &instruction.program_id)
+        {
+            PROGRAMS.with(|cell| {
+                if let Some(program) = cell
+                    .borrow()
+                    .iter()
+                    .find(|program| program.key == instruction.program_id)
+                {
+                    // The stub copies the read-only program account back unchanged, the copy
+                    // only has to live as long as the test.
+                    account_infos.push(AccountInfo::new(
+                        Box::leak(Box::new(program.key)),
+                        false,
+                        false,
+                        Box::leak(Box::new(program.lamports)),
+                        Box::leak(program.data.clone().into_boxed_slice()),
+                        Box::leak(Box::new(program.owner)),
+                        true,
+                        program.rent_epoch,
+                    ));
+                }
+            });
+        }
+        self.inner()
+            .sol_invoke_signed(instruction, &account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_clock_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_epoch_schedule_sysvar(var_addr)
+    }
+
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_fees_sysvar(var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_rent_sysvar(var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner().sol_get_return_data()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        // The runtime fails the instruction above this size, the stub takes anything.
+        assert!(
+            data.len() <= MAX_RETURN_DATA,
+            "{} bytes of return data",
+            data.len()
+        );
+        self.inner().sol_set_return_data(data)
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        self.inner().sol_log_data(fields)
+    }
+}
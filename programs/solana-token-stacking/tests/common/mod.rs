@@ -0,0 +1,610 @@
+//! In-process harness for the program tests.
+//!
+//! The program runs as a builtin, which can't resize accounts in a cross-program invocation,
+//! so instructions that create accounts can't run here. [`Genesis`] seeds the platform and
+//! its users in the state `initialize` and `register_user` leave them in instead. The tests
+//! of the creating instructions need the compiled program and only run with `test-bpf`.
+//!
+//! `EmptyBump` is left out of the failure tests since Anchor always derives the bumps.
+#![allow(dead_code)]
+
+mod builtin;
+
+use anchor_lang::prelude::{Clock, Pubkey, Rent};
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_sdk::transport::TransportError;
+use solana_sdk::{system_instruction, system_program};
+use solana_token_stacking::errors::CustomErrors;
+use solana_token_stacking::state::*;
+use solana_token_stacking::{pricing, BCDEV_DECIMALS, FCTR_DECIMALS};
+use solana_token_stacking_client::{instructions, pda};
+
+pub const ROUND_DURATION: u64 = 100;
+pub const REGISTRATION_PRICE: u64 = 1_000;
+pub const START_TIME: i64 = 1_650_000_000;
+pub const USER_LAMPORTS: u64 = 10_000_000_000;
+
+pub fn program_test() -> ProgramTest {
+    if cfg!(feature = "test-bpf") {
+        let mut program_test =
+            ProgramTest::new("solana_token_stacking", solana_token_stacking::ID, None);
+        program_test.prefer_bpf(true);
+        program_test
+    } else {
+        let mut program_test = ProgramTest::new(
+            "solana_token_stacking",
+            solana_token_stacking::ID,
+            processor!(builtin::process_instruction),
+        );
+        program_test.add_program(
+            "spl_token",
+            spl_token::ID,
+            processor!(spl_token::processor::Processor::process),
+        );
+        program_test
+    }
+}
+
+fn rent_exempt(len: usize) -> u64 {
+    Rent::default().minimum_balance(len)
+}
+
+fn program_account<T: AccountSerialize>(account: &T, len: usize) -> Account {
+    let mut data = Vec::with_capacity(len);
+    account.try_serialize(&mut data).unwrap();
+    assert!(data.len() <= len, "account doesn't fit into {} bytes", len);
+    data.resize(len, 0);
+    Account {
+        lamports: rent_exempt(len),
+        data,
+        owner: solana_token_stacking::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn packed_account<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    state.pack_into_slice(&mut data);
+    Account {
+        lamports: rent_exempt(T::LEN),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn token_account(mint: Pubkey, amount: u64) -> Account {
+    packed_account(spl_token::state::Account {
+        mint,
+        owner: pda::platform(),
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    })
+}
+
+fn mint(decimals: u8, supply: u64) -> Account {
+    packed_account(spl_token::state::Mint {
+        mint_authority: Some(pda::platform()).into(),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    })
+}
+
+pub fn bump(address_seeds: &[&[u8]]) -> u8 {
+    Pubkey::find_program_address(address_seeds, &solana_token_stacking::ID).1
+}
+
+/// The state `initialize` leaves the platform in.
+pub fn initialized_platform(authority: Pubkey) -> Platform {
+    Platform {
+        bump: bump(&[b"platform"]),
+        bump_fctr_mint: bump(&[b"fctr_mint"]),
+        bump_bcdev_mint: bump(&[b"bcdev_mint"]),
+        bump_sol_vault: bump(&[b"sol_vault"]),
+        bump_fctr_token_vault: bump(&[b"fctr_token_vault"]),
+        round_index: 0,
+        round_start: 0,
+        is_final: false,
+        round_duration: ROUND_DURATION,
+        fctr_token_total_amount: 0,
+        bcdev_token_total_amount: 0,
+        registration_price: REGISTRATION_PRICE,
+        max_active_grantors: DEFAULT_MAX_ACTIVE_GRANTORS,
+        max_grantors_history: DEFAULT_MAX_GRANTORS_HISTORY,
+        min_grant_ratio: DEFAULT_MIN_GRANT_RATIO,
+        max_grant_ratio: DEFAULT_MAX_GRANT_RATIO,
+        reclaim_grace_period: ROUND_DURATION,
+        grant_offer_ttl_rounds: DEFAULT_GRANT_OFFER_TTL_ROUNDS,
+        min_grantor_share_bps: DEFAULT_MIN_GRANTOR_SHARE_BPS,
+        max_grantor_share_bps: DEFAULT_MAX_GRANTOR_SHARE_BPS,
+        total_fctr_staked: 0,
+        active_stakers: 0,
+        total_fctr_granted: 0,
+        total_bcdev_rewards: 0,
+        total_sol_in: 0,
+        total_sol_out: 0,
+        total_fctr_pending: 0,
+        total_fctr_offered: 0,
+        is_paused: false,
+        authority,
+    }
+}
+
+/// Builds the genesis accounts of a platform with registered users.
+pub struct Genesis {
+    pub program_test: ProgramTest,
+    pub platform: Platform,
+    pub platform_authority: Keypair,
+    pub users: Vec<Keypair>,
+}
+
+impl Genesis {
+    pub fn new() -> Self {
+        let platform_authority = Keypair::new();
+        let mut program_test = program_test();
+        program_test.add_account(platform_authority.pubkey(), system_account(USER_LAMPORTS));
+        Genesis {
+            program_test,
+            platform: initialized_platform(platform_authority.pubkey()),
+            platform_authority,
+            users: Vec::new(),
+        }
+    }
+
+    /// Registers `count` users, all of them in the grant program.
+    pub fn with_users(mut self, count: usize) -> Self {
+        (0..count).for_each(|_| {
+            self.register_user(true);
+        });
+        self
+    }
+
+    /// Adds a funded wallet that still has to register, for the compiled program.
+    pub fn add_wallet(&mut self) -> &Keypair {
+        let keypair = Keypair::new();
+        self.program_test
+            .add_account(keypair.pubkey(), system_account(USER_LAMPORTS));
+        self.users.push(keypair);
+        self.users.last().unwrap()
+    }
+
+    pub fn register_user(&mut self, grant_program: bool) -> &Keypair {
+        let keypair = Keypair::new();
+        let authority = keypair.pubkey();
+        let max_active = self.platform.max_active_grantors as usize;
+        let history_capacity = self.platform.max_grantors_history as usize;
+
+        let mut grant_history = GrantHistory {
+            bump: bump(&[b"grant_history", authority.as_ref()]),
+            authority,
+            ..Default::default()
+        };
+        grant_history.resize(history_capacity);
+        let accounts = [
+            (authority, system_account(USER_LAMPORTS)),
+            (
+                pda::user(&authority),
+                program_account(
+                    &User {
+                        bump: bump(&[b"user", authority.as_ref()]),
+                        bump_fctr_vault: bump(&[b"fctr_vault", authority.as_ref()]),
+                        bump_bcdev_vault: bump(&[b"bcdev_vault", authority.as_ref()]),
+                        bump_receipt: bump(&[b"receipt", authority.as_ref()]),
+                        grant_program,
+                        user_fctr_amount: 0,
+                        next_position_id: 0,
+                        authority,
+                        operator: None,
+                    },
+                    8 + User::SPACE,
+                ),
+            ),
+            (
+                pda::receipt(&authority),
+                program_account(
+                    &Receipt {
+                        apr: 0.01,
+                        authority,
+                        ..Default::default()
+                    },
+                    8 + Receipt::space(max_active),
+                ),
+            ),
+            (
+                pda::grant_history(&authority),
+                program_account(&grant_history, 8 + GrantHistory::space(history_capacity)),
+            ),
+            (
+                pda::reputation(&authority),
+                program_account(
+                    &Reputation {
+                        bump: bump(&[b"reputation", authority.as_ref()]),
+                        authority,
+                        ..Default::default()
+                    },
+                    8 + Reputation::SPACE,
+                ),
+            ),
+            (
+                pda::user_stats(&authority),
+                program_account(
+                    &UserStats {
+                        bump: bump(&[b"user_stats", authority.as_ref()]),
+                        sol_paid: self.platform.registration_price,
+                        authority,
+                        ..Default::default()
+                    },
+                    8 + UserStats::SPACE,
+                ),
+            ),
+            (
+                pda::trust_list(&authority),
+                program_account(
+                    &TrustList {
+                        bump: bump(&[b"trust_list", authority.as_ref()]),
+                        authority,
+                        ..Default::default()
+                    },
+                    8 + TrustList::SPACE,
+                ),
+            ),
+            (
+                pda::fctr_vault(&authority),
+                token_account(pda::fctr_mint(), 0),
+            ),
+            (
+                pda::bcdev_vault(&authority),
+                token_account(pda::bcdev_mint(), 0),
+            ),
+        ];
+        accounts
+            .into_iter()
+            .for_each(|(address, account)| self.program_test.add_account(address, account));
+        self.platform.total_sol_in += self.platform.registration_price;
+        self.users.push(keypair);
+        self.users.last().unwrap()
+    }
+
+    pub async fn start(mut self) -> TestEnv {
+        let platform = &self.platform;
+        let sol_vault_lamports = rent_exempt(0) + platform.total_sol_in;
+        let accounts = [
+            (
+                pda::platform(),
+                program_account(platform, 8 + Platform::SPACE),
+            ),
+            (pda::sol_vault(), system_account(sol_vault_lamports)),
+            (pda::fctr_mint(), mint(FCTR_DECIMALS, 0)),
+            (pda::bcdev_mint(), mint(BCDEV_DECIMALS, 0)),
+            (pda::fctr_token_vault(), token_account(pda::fctr_mint(), 0)),
+        ];
+        accounts
+            .into_iter()
+            .for_each(|(address, account)| self.program_test.add_account(address, account));
+        self.start_without_platform().await
+    }
+
+    /// Starts with the users' wallets and accounts only, for the tests of `initialize`.
+    pub async fn start_without_platform(self) -> TestEnv {
+        let mut env = TestEnv {
+            context: self.program_test.start_with_context().await,
+            platform_authority: self.platform_authority,
+            users: self.users,
+            now: START_TIME,
+        };
+        env.set_time(START_TIME);
+        env
+    }
+}
+
+impl Default for Genesis {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn clone_keypair(keypair: &Keypair) -> Keypair {
+    Keypair::from_bytes(&keypair.to_bytes()).unwrap()
+}
+
+pub fn system_account(lamports: u64) -> Account {
+    Account {
+        lamports,
+        data: Vec::new(),
+        owner: system_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+    pub platform_authority: Keypair,
+    pub users: Vec<Keypair>,
+    pub now: i64,
+}
+
+impl TestEnv {
+    pub fn user(&self, index: usize) -> Pubkey {
+        self.users[index].pubkey()
+    }
+
+    /// Moves the cluster clock, which is what every round and stake check reads.
+    pub fn set_time(&mut self, unix_timestamp: i64) {
+        self.now = unix_timestamp;
+        self.context.set_sysvar(&Clock {
+            unix_timestamp,
+            ..Clock::default()
+        });
+    }
+
+    pub fn warp(&mut self, seconds: i64) {
+        self.set_time(self.now + seconds);
+    }
+
+    /// Sends `instruction` signed by the fee payer and `signers`.
+    pub async fn send(
+        &mut self,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<(), TransportError> {
+        // An empty transfer to a fresh address tells identical transactions apart.
+        let nonce =
+            system_instruction::transfer(&self.context.payer.pubkey(), &Pubkey::new_unique(), 0);
+        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction, nonce],
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    pub async fn send_as_user(
+        &mut self,
+        index: usize,
+        instruction: Instruction,
+    ) -> Result<(), TransportError> {
+        let user = clone_keypair(&self.users[index]);
+        self.send(instruction, &[&user]).await
+    }
+
+    pub async fn send_as_authority(
+        &mut self,
+        instruction: Instruction,
+    ) -> Result<(), TransportError> {
+        let authority = clone_keypair(&self.platform_authority);
+        self.send(instruction, &[&authority]).await
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {} doesn't exist", address));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn exists(&mut self, address: Pubkey) -> bool {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .is_some_and(|account| account.lamports > 0)
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn token_amount(&mut self, address: Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    pub async fn mint_supply(&mut self, address: Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        spl_token::state::Mint::unpack(&account.data)
+            .unwrap()
+            .supply
+    }
+
+    pub async fn platform(&mut self) -> Platform {
+        self.account(pda::platform()).await
+    }
+
+    pub async fn receipt(&mut self, index: usize) -> Receipt {
+        let authority = self.user(index);
+        self.account(pda::receipt(&authority)).await
+    }
+
+    pub async fn user_stats(&mut self, index: usize) -> UserStats {
+        let authority = self.user(index);
+        self.account(pda::user_stats(&authority)).await
+    }
+
+    pub async fn fctr_balance(&mut self, index: usize) -> u64 {
+        let authority = self.user(index);
+        self.token_amount(pda::fctr_vault(&authority)).await
+    }
+
+    pub async fn bcdev_balance(&mut self, index: usize) -> u64 {
+        let authority = self.user(index);
+        self.token_amount(pda::bcdev_vault(&authority)).await
+    }
+
+    pub async fn start_round(&mut self) {
+        let authority = self.platform_authority.pubkey();
+        self.send_as_authority(instructions::start_round(&authority, false))
+            .await
+            .unwrap();
+    }
+
+    /// Buys FCTR for `lamports` and returns the amount minted.
+    pub async fn buy(&mut self, index: usize, lamports: u64) -> u64 {
+        let authority = self.user(index);
+        self.send_as_user(index, instructions::buy_tokens(&authority, lamports))
+            .await
+            .unwrap();
+        pricing::fctr_for_lamports(lamports).unwrap()
+    }
+
+    /// Allows `grantors` on the trust list of `confidant`, so they can grant to him directly.
+    pub async fn trust(&mut self, confidant: usize, grantors: &[usize]) {
+        let authority = self.user(confidant);
+        let entries = grantors
+            .iter()
+            .map(|&grantor| TrustEntry {
+                user: self.user(grantor),
+                allowed: true,
+            })
+            .collect();
+        self.send_as_user(
+            confidant,
+            instructions::update_trust_list(&authority, TrustListMode::Open, entries),
+        )
+        .await
+        .unwrap();
+    }
+
+    pub async fn stake(&mut self, index: usize) -> Result<(), TransportError> {
+        let authority = self.user(index);
+        self.send_as_user(index, instructions::stake(&authority, &authority))
+            .await
+    }
+
+    /// Unstakes with the accounts of the grantors on the user's receipt.
+    pub async fn unstake(&mut self, index: usize) -> Result<(), TransportError> {
+        let authority = self.user(index);
+        let receipt = self.receipt(index).await;
+        let mut grantors = Vec::with_capacity(receipt.grantors.len());
+        for grantor in &receipt.grantors {
+            grantors.push(self.account::<User>(grantor.grantor).await.authority);
+        }
+        self.send_as_user(
+            index,
+            instructions::unstake(&authority, &authority, &grantors),
+        )
+        .await
+    }
+
+    /// Overwrites a program account, for state that only account-creating instructions reach.
+    pub fn set_program_account<T: AccountSerialize>(
+        &mut self,
+        address: Pubkey,
+        account: &T,
+        len: usize,
+    ) {
+        self.context.set_account(
+            &address,
+            &AccountSharedData::from(program_account(account, len)),
+        );
+    }
+
+    pub async fn update_receipt(&mut self, index: usize, update: impl FnOnce(&mut Receipt)) {
+        let address = pda::receipt(&self.user(index));
+        let len = self.account_len(address).await;
+        let mut receipt = self.receipt(index).await;
+        update(&mut receipt);
+        self.set_program_account(address, &receipt, len);
+    }
+
+    async fn account_len(&mut self, address: Pubkey) -> usize {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap()
+            .data
+            .len()
+    }
+
+    /// Overwrites the balance of a token account held by the platform.
+    pub async fn set_token_amount(&mut self, address: Pubkey, amount: u64) {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        let state = spl_token::state::Account::unpack(&account.data).unwrap();
+        self.context.set_account(
+            &address,
+            &AccountSharedData::from(token_account(state.mint, amount)),
+        );
+    }
+
+    pub async fn update_platform(&mut self, update: impl FnOnce(&mut Platform)) {
+        let mut platform = self.platform().await;
+        update(&mut platform);
+        self.set_program_account(pda::platform(), &platform, 8 + Platform::SPACE);
+    }
+}
+
+/// Asserts that a transaction failed with the given program error.
+pub fn assert_custom_error(result: Result<(), TransportError>, error: CustomErrors) {
+    let expected = anchor_lang::error::ERROR_CODE_OFFSET + error as u32;
+    match result {
+        Err(TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, expected, "expected {:?}", error),
+        other => panic!("expected {:?}, got {:?}", error, other),
+    }
+}
+
+/// Asserts that a transaction failed with the given Anchor framework error.
+pub fn assert_anchor_error(
+    result: Result<(), TransportError>,
+    error: anchor_lang::error::ErrorCode,
+) {
+    match result {
+        Err(TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(code),
+        ))) => assert_eq!(code, error as u32, "expected {:?}", error),
+        other => panic!("expected {:?}, got {:?}", error, other),
+    }
+}
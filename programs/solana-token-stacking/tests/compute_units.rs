@@ -0,0 +1,333 @@
+//! Compute units of the instructions, checked against a baseline per runtime:
+//! `tests/compute_units.json` for the compiled program (`cargo build-bpf` and then
+//! `cargo test --features test-bpf`) and `tests/compute_units.builtin.json` for the builtin.
+//!
+//! Only the compiled program meters its own execution. The builtin is charged for its sysvar
+//! reads alone, so its baseline only catches instructions reading more sysvars, but it keeps
+//! the scenarios running with plain `cargo test`. `register_user` creates accounts and is only
+//! measured on the compiled program.
+//!
+//! A benchmark fails once it consumes more than `TOLERANCE_PERCENT` over its baseline; one
+//! without a baseline entry is reported and passes. `UPDATE_COMPUTE_UNITS=1` records the
+//! current numbers as the new baseline of the runtime. `--nocapture` prints every benchmark
+//! along with the headroom left to the compute budget where the runtime reports it.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_token_stacking::state::{GrantHistory, GrantorHistoryRecord, User};
+use solana_token_stacking_client::{instructions, pda};
+use std::collections::BTreeMap;
+
+#[cfg(feature = "test-bpf")]
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/compute_units.json");
+#[cfg(not(feature = "test-bpf"))]
+const BASELINE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/compute_units.builtin.json"
+);
+const TOLERANCE_PERCENT: u64 = 5;
+const GRANTOR_SHARE_BPS: u16 = 5_000;
+
+struct Measurement {
+    units: u64,
+    /// Only the compiled program logs its compute budget.
+    budget: Option<u64>,
+}
+
+#[derive(Default)]
+struct Benchmarks(BTreeMap<String, Measurement>);
+
+impl Benchmarks {
+    /// Measures `instruction` and then executes it for the benchmarks that follow.
+    async fn run(
+        &mut self,
+        name: &str,
+        env: &mut TestEnv,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) {
+        let measurement = measure(env, instruction.clone(), signers).await;
+        env.send(instruction, signers).await.unwrap();
+        self.0.insert(name.to_string(), measurement);
+    }
+}
+
+/// The banks client only reports compute units for transactions that fail in simulation, so
+/// `instruction` is simulated ahead of a transfer that can't succeed, which consumes none.
+/// Nothing of the simulation is committed.
+async fn measure(env: &mut TestEnv, instruction: Instruction, signers: &[&Keypair]) -> Measurement {
+    let payer = env.context.payer.pubkey();
+    let failing = system_instruction::transfer(&payer, &Pubkey::new_unique(), u64::MAX);
+    let blockhash = env
+        .context
+        .banks_client
+        .get_latest_blockhash()
+        .await
+        .unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction, failing],
+        Some(&payer),
+        &[&[&env.context.payer], signers].concat(),
+        blockhash,
+    );
+    let (logs, units) = match env
+        .context
+        .banks_client
+        .process_transaction_with_preflight(transaction)
+        .await
+    {
+        Err(BanksClientError::SimulationError {
+            err: TransactionError::InstructionError(1, _),
+            logs,
+            units_consumed,
+        }) => (logs, units_consumed),
+        other => panic!("the measured instruction failed: {:?}", other),
+    };
+    let prefix = format!("Program {} consumed ", solana_token_stacking::ID);
+    let budget = logs.iter().find_map(|log| {
+        let consumed = log.strip_prefix(&prefix)?.strip_suffix(" compute units")?;
+        consumed.split_once(" of ")?.1.parse().ok()
+    });
+    Measurement { units, budget }
+}
+
+/// Registers a wallet, where the runtime can create its accounts, and runs it through a round
+/// of staking and selling.
+async fn staking_and_trading(benchmarks: &mut Benchmarks) {
+    let mut genesis = Genesis::new();
+    if cfg!(feature = "test-bpf") {
+        genesis.add_wallet();
+    } else {
+        genesis.register_user(true);
+    }
+    let mut env = genesis.start().await;
+    let user = clone_keypair(&env.users[0]);
+    let authority = user.pubkey();
+    let platform_authority = clone_keypair(&env.platform_authority);
+    // FCTR sells back above its buying price, so the vault needs liquidity to cover it.
+    env.send_as_authority(instructions::add_liquidity(
+        &platform_authority.pubkey(),
+        1_000_000,
+    ))
+    .await
+    .unwrap();
+
+    if cfg!(feature = "test-bpf") {
+        benchmarks
+            .run(
+                "register_user",
+                &mut env,
+                instructions::register_user(&authority, true),
+                &[&user],
+            )
+            .await;
+    }
+    benchmarks
+        .run(
+            "buy_tokens",
+            &mut env,
+            instructions::buy_tokens(&authority, 1_000_000),
+            &[&user],
+        )
+        .await;
+    benchmarks
+        .run(
+            "start_round",
+            &mut env,
+            instructions::start_round(&platform_authority.pubkey(), false),
+            &[&platform_authority],
+        )
+        .await;
+    benchmarks
+        .run(
+            "stake",
+            &mut env,
+            instructions::stake(&authority, &authority),
+            &[&user],
+        )
+        .await;
+    env.warp(ROUND_DURATION as i64 + 1);
+    env.unstake(0).await.unwrap();
+
+    let bcdev = env.bcdev_balance(0).await;
+    benchmarks
+        .run(
+            "sell_bcdev_tokens",
+            &mut env,
+            instructions::sell_bcdev_tokens(&authority, bcdev / 2),
+            &[&user],
+        )
+        .await;
+    benchmarks
+        .run(
+            "sell_fctr_tokens",
+            &mut env,
+            instructions::sell_fctr_tokens(&authority),
+            &[&user],
+        )
+        .await;
+}
+
+/// Grants into an active stake and claims the grant back, with the confidant's grant history
+/// empty or filled up to its capacity with other grantors.
+async fn grants(benchmarks: &mut Benchmarks, full_history: bool) {
+    let mut env = Genesis::new().with_users(2).start().await;
+    let grantor = clone_keypair(&env.users[1]);
+    let confidant = env.user(0);
+    let minted = env.buy(0, 1_000_000).await;
+    env.buy(1, 1_000_000).await;
+    env.trust(0, &[1]).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+
+    let suffix = if full_history {
+        let address = pda::grant_history(&confidant);
+        let mut history: GrantHistory = env.account(address).await;
+        let capacity = history.capacity();
+        (0..capacity).for_each(|_| {
+            history.push(GrantorHistoryRecord {
+                grant_ts: env.now as u64,
+                grantor: Pubkey::new_unique(),
+            })
+        });
+        env.set_program_account(address, &history, 8 + GrantHistory::space(capacity));
+        "_full_history"
+    } else {
+        ""
+    };
+
+    benchmarks
+        .run(
+            &format!("grant_tokens{}", suffix),
+            &mut env,
+            instructions::grant_tokens(
+                &grantor.pubkey(),
+                &confidant,
+                minted / 2,
+                GRANTOR_SHARE_BPS,
+            ),
+            &[&grantor],
+        )
+        .await;
+    benchmarks
+        .run(
+            &format!("claim_tokens{}", suffix),
+            &mut env,
+            instructions::claim_tokens(&grantor.pubkey(), &grantor.pubkey(), &confidant),
+            &[&grantor],
+        )
+        .await;
+}
+
+/// Unstakes a receipt holding a grant from each of `grantors` grantors.
+async fn unstake(benchmarks: &mut Benchmarks, grantors: usize) {
+    let mut env = Genesis::new().with_users(grantors + 1).start().await;
+    let user = clone_keypair(&env.users[0]);
+    let confidant = user.pubkey();
+    let mut minted = 0;
+    for index in 0..=grantors {
+        minted = env.buy(index, 1_000_000).await;
+    }
+    env.trust(0, &(1..=grantors).collect::<Vec<_>>()).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    for index in 1..=grantors {
+        let authority = env.user(index);
+        env.send_as_user(
+            index,
+            instructions::grant_tokens(&authority, &confidant, minted / 2, GRANTOR_SHARE_BPS),
+        )
+        .await
+        .unwrap();
+    }
+    env.warp(ROUND_DURATION as i64 + 1);
+
+    let receipt = env.receipt(0).await;
+    assert_eq!(receipt.grantors.len(), grantors);
+    let mut wallets = Vec::with_capacity(grantors);
+    for grantor in &receipt.grantors {
+        wallets.push(env.account::<User>(grantor.grantor).await.authority);
+    }
+    benchmarks
+        .run(
+            &format!("project_reward_{}_grantors", grantors),
+            &mut env,
+            instructions::project_reward(&confidant),
+            &[],
+        )
+        .await;
+    benchmarks
+        .run(
+            &format!("unstake_{}_grantors", grantors),
+            &mut env,
+            instructions::unstake(&confidant, &confidant, &wallets),
+            &[&user],
+        )
+        .await;
+}
+
+fn read_baseline() -> Option<BTreeMap<String, u64>> {
+    let json = std::fs::read_to_string(BASELINE).ok()?;
+    Some(serde_json::from_str(&json).unwrap())
+}
+
+fn write_baseline(benchmarks: &Benchmarks) {
+    let baseline = benchmarks
+        .0
+        .iter()
+        .map(|(name, measurement)| (name, measurement.units))
+        .collect::<BTreeMap<_, _>>();
+    let json = serde_json::to_string_pretty(&baseline).unwrap();
+    std::fs::write(BASELINE, json + "\n").unwrap();
+}
+
+#[tokio::test]
+async fn compute_units_stay_within_the_baseline() {
+    let mut benchmarks = Benchmarks::default();
+    staking_and_trading(&mut benchmarks).await;
+    grants(&mut benchmarks, false).await;
+    grants(&mut benchmarks, true).await;
+    for grantors in 0..=solana_token_stacking::state::DEFAULT_MAX_ACTIVE_GRANTORS as usize {
+        unstake(&mut benchmarks, grantors).await;
+    }
+
+    if std::env::var_os("UPDATE_COMPUTE_UNITS").is_some() {
+        write_baseline(&benchmarks);
+        println!("compute units recorded in {}", BASELINE);
+        return;
+    }
+    let baseline = read_baseline().unwrap_or_default();
+    let mut regressions = Vec::new();
+    for (name, Measurement { units, budget }) in &benchmarks.0 {
+        let expected = baseline.get(name).copied();
+        println!(
+            "{:<32} {:>8} units  baseline {:>8}{}",
+            name,
+            units,
+            expected.map_or_else(|| "-".to_string(), |units| units.to_string()),
+            budget.map_or_else(String::new, |budget| format!(
+                "  {:>5.1}% of the budget",
+                *units as f64 * 100.0 / budget as f64
+            )),
+        );
+        if let Some(expected) = expected {
+            if units * 100 > expected * (100 + TOLERANCE_PERCENT) {
+                regressions.push(format!("{}: {} > {}", name, units, expected));
+            }
+        }
+    }
+    assert!(
+        regressions.is_empty(),
+        "compute units over the baseline by more than {}%, rerun with UPDATE_COMPUTE_UNITS=1 \
+         if that is expected: {:?}",
+        TOLERANCE_PERCENT,
+        regressions
+    );
+}
@@ -0,0 +1,373 @@
+//! Random instruction sequences across several users, checking the platform-wide invariants
+//! after every step.
+//!
+//! Failed instructions are part of the game, only a broken invariant fails a sequence, which
+//! proptest then shrinks to the shortest one it can find. `PROPTEST_CASES` runs longer
+//! campaigns. With `test-bpf` the sequences run against the compiled program and users start
+//! unregistered; as a builtin, where `register_user` can't create accounts, they start
+//! registered and registering is skipped. Grants only go through once a `Trust` step has let
+//! the confidant accept them from the grantor.
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use common::*;
+use proptest::bool::weighted;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use solana_sdk::signature::Signer;
+use solana_token_stacking::state::{Receipt, TrustEntry, TrustListMode, User, BPS_DENOMINATOR};
+use solana_token_stacking_client::{instructions, pda};
+
+const USERS: usize = 4;
+const MAX_STEPS: usize = 40;
+
+#[derive(Clone, Debug)]
+enum Step {
+    Register {
+        user: usize,
+        grant_program: bool,
+    },
+    Buy {
+        user: usize,
+        lamports: u64,
+    },
+    Stake {
+        user: usize,
+    },
+    /// Replaces the trust list of `confidant` with an entry per user.
+    Trust {
+        confidant: usize,
+        allow_list_only: bool,
+        allowed: [bool; USERS],
+    },
+    Grant {
+        grantor: usize,
+        confidant: usize,
+        part_bps: u16,
+        grantor_share_bps: u16,
+    },
+    Claim {
+        grantor: usize,
+        confidant: usize,
+    },
+    Unstake {
+        user: usize,
+    },
+    SellFctr {
+        user: usize,
+    },
+    SellBcdev {
+        user: usize,
+        part_bps: u16,
+    },
+    StartRound,
+    Warp {
+        seconds: u64,
+    },
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    let user = 0..USERS;
+    let part_bps = 1..=BPS_DENOMINATOR;
+    prop_oneof![
+        1 => (user.clone(), any::<bool>())
+            .prop_map(|(user, grant_program)| Step::Register { user, grant_program }),
+        3 => (user.clone(), 1..=1_000_000u64).prop_map(|(user, lamports)| Step::Buy { user, lamports }),
+        2 => user.clone().prop_map(|user| Step::Stake { user }),
+        // Mostly permissive lists, so that grants get through often enough to matter.
+        4 => (user.clone(), weighted(0.2), [weighted(0.8); USERS]).prop_map(
+            |(confidant, allow_list_only, allowed)| Step::Trust {
+                confidant,
+                allow_list_only,
+                allowed,
+            }
+        ),
+        3 => (user.clone(), user.clone(), part_bps.clone(), 0..=BPS_DENOMINATOR).prop_map(
+            |(grantor, confidant, part_bps, grantor_share_bps)| Step::Grant {
+                grantor,
+                confidant,
+                part_bps,
+                grantor_share_bps,
+            }
+        ),
+        2 => (user.clone(), user.clone())
+            .prop_map(|(grantor, confidant)| Step::Claim { grantor, confidant }),
+        2 => user.clone().prop_map(|user| Step::Unstake { user }),
+        1 => user.clone().prop_map(|user| Step::SellFctr { user }),
+        1 => (user, part_bps).prop_map(|(user, part_bps)| Step::SellBcdev { user, part_bps }),
+        1 => Just(Step::StartRound),
+        2 => (1..=2 * ROUND_DURATION).prop_map(|seconds| Step::Warp { seconds }),
+    ]
+}
+
+async fn play(env: &mut TestEnv, step: &Step) {
+    // Whether an instruction succeeds doesn't matter here, the invariants do.
+    let _ = match *step {
+        Step::Register {
+            user,
+            grant_program,
+        } => {
+            if !cfg!(feature = "test-bpf") {
+                return;
+            }
+            let authority = env.user(user);
+            env.send_as_user(user, instructions::register_user(&authority, grant_program))
+                .await
+        }
+        Step::Buy { user, lamports } => {
+            let authority = env.user(user);
+            env.send_as_user(user, instructions::buy_tokens(&authority, lamports))
+                .await
+        }
+        Step::Stake { user } => env.stake(user).await,
+        Step::Trust {
+            confidant,
+            allow_list_only,
+            allowed,
+        } => {
+            let authority = env.user(confidant);
+            let mode = if allow_list_only {
+                TrustListMode::AllowListOnly
+            } else {
+                TrustListMode::Open
+            };
+            let entries = allowed
+                .iter()
+                .enumerate()
+                .map(|(user, &allowed)| TrustEntry {
+                    user: env.user(user),
+                    allowed,
+                })
+                .collect();
+            env.send_as_user(
+                confidant,
+                instructions::update_trust_list(&authority, mode, entries),
+            )
+            .await
+        }
+        Step::Grant {
+            grantor,
+            confidant,
+            part_bps,
+            grantor_share_bps,
+        } => {
+            let authority = env.user(grantor);
+            let confidant_authority = env.user(confidant);
+            let amount = part(
+                token_balance(env, pda::fctr_vault(&authority)).await,
+                part_bps,
+            );
+            env.send_as_user(
+                grantor,
+                instructions::grant_tokens(
+                    &authority,
+                    &confidant_authority,
+                    amount,
+                    grantor_share_bps,
+                ),
+            )
+            .await
+        }
+        Step::Claim { grantor, confidant } => {
+            let authority = env.user(grantor);
+            env.send_as_user(
+                grantor,
+                instructions::claim_tokens(&authority, &authority, &env.user(confidant)),
+            )
+            .await
+        }
+        Step::Unstake { user } => {
+            let authority = env.user(user);
+            let grantors = receipt_grantors(env, &authority).await;
+            env.send_as_user(
+                user,
+                instructions::unstake(&authority, &authority, &grantors),
+            )
+            .await
+        }
+        Step::SellFctr { user } => {
+            let authority = env.user(user);
+            env.send_as_user(user, instructions::sell_fctr_tokens(&authority))
+                .await
+        }
+        Step::SellBcdev { user, part_bps } => {
+            let authority = env.user(user);
+            let amount = part(
+                token_balance(env, pda::bcdev_vault(&authority)).await,
+                part_bps,
+            );
+            env.send_as_user(user, instructions::sell_bcdev_tokens(&authority, amount))
+                .await
+        }
+        Step::StartRound => {
+            let authority = env.platform_authority.pubkey();
+            env.send_as_authority(instructions::start_round(&authority, false))
+                .await
+        }
+        Step::Warp { seconds } => {
+            env.warp(seconds as i64);
+            Ok(())
+        }
+    };
+}
+
+fn part(amount: u64, part_bps: u16) -> u64 {
+    (amount as u128 * part_bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+async fn token_balance(env: &mut TestEnv, address: Pubkey) -> u64 {
+    if env.exists(address).await {
+        env.token_amount(address).await
+    } else {
+        0
+    }
+}
+
+/// Wallets of the grantors on a receipt, as `unstake` expects them.
+async fn receipt_grantors(env: &mut TestEnv, authority: &Pubkey) -> Vec<Pubkey> {
+    if !env.exists(pda::receipt(authority)).await {
+        return Vec::new();
+    }
+    let receipt: Receipt = env.account(pda::receipt(authority)).await;
+    let mut grantors = Vec::with_capacity(receipt.grantors.len());
+    for grantor in &receipt.grantors {
+        grantors.push(env.account::<User>(grantor.grantor).await.authority);
+    }
+    grantors
+}
+
+/// Every account an instruction of the sequence can move lamports in or out of.
+fn lamport_holders(env: &TestEnv) -> Vec<Pubkey> {
+    let platform = [
+        env.context.payer.pubkey(),
+        env.platform_authority.pubkey(),
+        pda::platform(),
+        pda::sol_vault(),
+        pda::fctr_mint(),
+        pda::bcdev_mint(),
+        pda::fctr_token_vault(),
+    ];
+    let users = env.users.iter().flat_map(|user| {
+        let authority = user.pubkey();
+        [
+            authority,
+            pda::user(&authority),
+            pda::receipt(&authority),
+            pda::grant_history(&authority),
+            pda::reputation(&authority),
+            pda::user_stats(&authority),
+            pda::trust_list(&authority),
+            pda::fctr_vault(&authority),
+            pda::bcdev_vault(&authority),
+        ]
+    });
+    platform.into_iter().chain(users).collect()
+}
+
+async fn total_lamports(env: &mut TestEnv) -> u64 {
+    let mut total = 0;
+    for address in lamport_holders(env) {
+        total += env.lamports(address).await;
+    }
+    total
+}
+
+async fn check_invariants(
+    env: &mut TestEnv,
+    lamports_before: u64,
+    step: &Step,
+) -> Result<u64, TestCaseError> {
+    let platform = env.platform().await;
+    let fctr_supply = env.mint_supply(pda::fctr_mint()).await;
+    let bcdev_supply = env.mint_supply(pda::bcdev_mint()).await;
+    prop_assert_eq!(
+        fctr_supply,
+        platform.fctr_token_total_amount,
+        "FCTR supply after {:?}",
+        step
+    );
+    prop_assert_eq!(
+        bcdev_supply,
+        platform.bcdev_token_total_amount,
+        "BCDEV supply after {:?}",
+        step
+    );
+
+    let platform_vault = env.token_amount(pda::fctr_token_vault()).await;
+    prop_assert_eq!(
+        platform_vault,
+        platform.total_fctr_staked + platform.total_fctr_pending + platform.total_fctr_offered,
+        "platform FCTR vault after {:?}",
+        step
+    );
+    let mut fctr_held = platform_vault;
+    let mut bcdev_held = 0;
+    for index in 0..env.users.len() {
+        let authority = env.user(index);
+        fctr_held += token_balance(env, pda::fctr_vault(&authority)).await;
+        bcdev_held += token_balance(env, pda::bcdev_vault(&authority)).await;
+    }
+    prop_assert_eq!(fctr_held, fctr_supply, "FCTR held after {:?}", step);
+    prop_assert_eq!(bcdev_held, bcdev_supply, "BCDEV held after {:?}", step);
+
+    let rent = Rent::default().minimum_balance(0);
+    let sol_vault = env.lamports(pda::sol_vault()).await;
+    prop_assert!(sol_vault >= rent, "SOL vault below rent after {:?}", step);
+    prop_assert_eq!(
+        sol_vault,
+        rent + platform.total_sol_in - platform.total_sol_out,
+        "SOL vault after {:?}",
+        step
+    );
+    // Fees leave the tracked accounts, nothing may enter them.
+    let lamports = total_lamports(env).await;
+    prop_assert!(
+        lamports <= lamports_before,
+        "{} lamports created by {:?}",
+        lamports - lamports_before,
+        step
+    );
+    Ok(lamports)
+}
+
+async fn run(steps: Vec<Step>) -> Result<(), TestCaseError> {
+    let mut genesis = Genesis::new();
+    (0..USERS).for_each(|_| {
+        if cfg!(feature = "test-bpf") {
+            genesis.add_wallet();
+        } else {
+            genesis.register_user(true);
+        }
+    });
+    let mut env = genesis.start().await;
+    let mut lamports = total_lamports(&mut env).await;
+    for step in &steps {
+        play(&mut env, step).await;
+        lamports = check_invariants(&mut env, lamports, step).await?;
+    }
+    Ok(())
+}
+
+/// A few dozen sequences by default, `PROPTEST_CASES` asks for more.
+fn config() -> ProptestConfig {
+    let default = ProptestConfig::default();
+    ProptestConfig {
+        cases: std::env::var("PROPTEST_CASES").map_or(32, |_| default.cases),
+        ..default
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn instruction_sequences_keep_the_platform_consistent(
+        steps in proptest::collection::vec(step(), 1..=MAX_STEPS),
+    ) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(run(steps))?;
+    }
+}
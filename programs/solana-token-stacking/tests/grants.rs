@@ -0,0 +1,753 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+use solana_sdk::transport::TransportError;
+use solana_token_stacking::errors::CustomErrors;
+use solana_token_stacking::state::{
+    GrantHistory, GrantOffer, GrantorHistoryRecord, Reputation, TrustEntry, TrustListMode,
+    DEFAULT_GRANT_OFFER_TTL_ROUNDS, DEFAULT_MAX_ACTIVE_GRANTORS, DEFAULT_MAX_GRANTORS_HISTORY,
+    DEFAULT_MAX_GRANTOR_SHARE_BPS, DEFAULT_MAX_GRANT_RATIO, DEFAULT_MIN_GRANTOR_SHARE_BPS,
+    DEFAULT_MIN_GRANT_RATIO, MAX_TRUST_LIST_ENTRIES,
+};
+use solana_token_stacking_client::{instructions, pda};
+
+const GRANTOR_SHARE_BPS: u16 = 5_000;
+
+async fn grant(
+    env: &mut TestEnv,
+    grantor: usize,
+    confidant: usize,
+    amount: u64,
+    grantor_share_bps: u16,
+) -> Result<(), TransportError> {
+    let authority = env.user(grantor);
+    let confidant = env.user(confidant);
+    env.send_as_user(
+        grantor,
+        instructions::grant_tokens(&authority, &confidant, amount, grantor_share_bps),
+    )
+    .await
+}
+
+/// Registers `users` users, each holding the FCTR bought for the same amount of SOL and trusting
+/// all the others with direct grants.
+async fn funded_env(users: usize) -> (TestEnv, u64) {
+    let mut env = Genesis::new().with_users(users).start().await;
+    let mut minted = 0;
+    for index in 0..users {
+        minted = env.buy(index, 1_000_000).await;
+        let others = (0..users)
+            .filter(|&other| other != index)
+            .collect::<Vec<_>>();
+        env.trust(index, &others).await;
+    }
+    (env, minted)
+}
+
+#[tokio::test]
+async fn grants_to_an_unstaked_user_join_the_next_stake() {
+    let (mut env, minted) = funded_env(3).await;
+    let confidant = env.user(0);
+    let granted = minted / 4;
+    grant(&mut env, 1, 0, granted, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    grant(&mut env, 2, 0, granted, 2_000).await.unwrap();
+
+    let receipt = env.receipt(0).await;
+    assert!(receipt.grantors.is_empty());
+    assert_eq!(receipt.next_round_grantors.len(), 2);
+    assert_eq!(env.fctr_balance(1).await, minted - granted);
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_pending, 2 * granted);
+    assert_eq!(platform.total_fctr_granted, 2 * granted);
+    // Granting raises the grantor's APR by the share of his balance he gave away.
+    let grantor_receipt = env.receipt(1).await;
+    let expected_apr = 0.01 * minted as f64 / (minted - granted) as f64;
+    assert!((grantor_receipt.apr - expected_apr).abs() < 1e-12);
+
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+
+    let receipt = env.receipt(0).await;
+    assert_eq!(receipt.grantors.len(), 2);
+    assert!(receipt.next_round_grantors.is_empty());
+    assert!(receipt
+        .grantors
+        .iter()
+        .all(|g| g.grant_duration == ROUND_DURATION));
+    assert_eq!(receipt.amount_deposited, minted + 2 * granted);
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_pending, 0);
+    assert_eq!(platform.total_fctr_staked, minted + 2 * granted);
+
+    env.warp(ROUND_DURATION as i64 + 1);
+    env.unstake(0).await.unwrap();
+
+    assert_eq!(env.fctr_balance(0).await, minted);
+    assert_eq!(env.fctr_balance(1).await, minted);
+    assert_eq!(env.fctr_balance(2).await, minted);
+    let staker_reward = env.bcdev_balance(0).await;
+    let first_reward = env.bcdev_balance(1).await;
+    let second_reward = env.bcdev_balance(2).await;
+    // Both grants are equal, so the rewards follow the negotiated shares.
+    assert!((first_reward * 2_000).abs_diff(second_reward * 5_000) <= 5_000);
+    // Each grant adds to the staker's APR, so he earns more than staking alone.
+    assert!(staker_reward > (minted as f64 * 0.01) as u64);
+    assert_eq!(env.user_stats(1).await.bcdev_earned, first_reward);
+    assert_eq!(env.user_stats(2).await.bcdev_earned, second_reward);
+    let platform = env.platform().await;
+    assert_eq!(
+        platform.total_bcdev_rewards,
+        staker_reward + first_reward + second_reward
+    );
+    assert_eq!(platform.total_fctr_staked, 0);
+    assert_eq!(platform.total_fctr_granted, 0);
+    let reputation: Reputation = env.account(pda::reputation(&confidant)).await;
+    assert_eq!(reputation.grants_received, 2);
+    assert_eq!(reputation.fctr_entrusted, 2 * granted);
+    assert_eq!(reputation.on_time_unstakes, 1);
+}
+
+#[tokio::test]
+async fn grants_into_an_active_stake_are_staked_for_the_rest_of_the_round() {
+    let (mut env, minted) = funded_env(2).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.warp(60);
+
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+
+    let receipt = env.receipt(0).await;
+    assert_eq!(receipt.grantors.len(), 1);
+    assert_eq!(receipt.grantors[0].grant_duration, ROUND_DURATION - 60);
+    assert_eq!(receipt.amount_deposited, minted + minted / 2);
+    assert_eq!(env.platform().await.total_fctr_staked, minted + minted / 2);
+
+    env.warp(ROUND_DURATION as i64);
+    env.unstake(0).await.unwrap();
+    assert_eq!(env.fctr_balance(1).await, minted);
+    assert!(env.bcdev_balance(1).await > 0);
+}
+
+#[tokio::test]
+async fn rejects_unstaking_without_every_grantor() {
+    let (mut env, minted) = funded_env(2).await;
+    let confidant = env.user(0);
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.warp(ROUND_DURATION as i64 + 1);
+
+    let result = env
+        .send_as_user(0, instructions::unstake(&confidant, &confidant, &[]))
+        .await;
+
+    assert_custom_error(result, CustomErrors::InvalidGrantorsList);
+}
+
+#[tokio::test]
+async fn rejects_unstaking_with_foreign_grantor_accounts() {
+    let (mut env, minted) = funded_env(3).await;
+    let confidant = env.user(0);
+    let first = env.user(1);
+    let second = env.user(2);
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    grant(&mut env, 2, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.warp(ROUND_DURATION as i64 + 1);
+
+    // The staker's own vault in place of the grantor's.
+    let mut instruction = instructions::unstake(&confidant, &confidant, &[first, second]);
+    let fctr_vault = instruction.accounts.len() - 3;
+    instruction.accounts[fctr_vault].pubkey = pda::fctr_vault(&confidant);
+    let result = env.send_as_user(0, instruction).await;
+    assert_custom_error(result, CustomErrors::InvalidGrantorsList);
+
+    let result = env
+        .send_as_user(
+            0,
+            instructions::unstake(&confidant, &confidant, &[first, first]),
+        )
+        .await;
+    assert_custom_error(result, CustomErrors::InvalidGrantorsList);
+
+    let mut instruction = instructions::unstake(&confidant, &confidant, &[first, second]);
+    instruction.accounts.push(instruction.accounts[0].clone());
+    let result = env.send_as_user(0, instruction).await;
+    assert_custom_error(result, CustomErrors::InvalidGrantorsList);
+
+    env.send_as_user(
+        0,
+        instructions::unstake(&confidant, &confidant, &[second, first]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.fctr_balance(1).await, minted);
+    assert_eq!(env.fctr_balance(2).await, minted);
+}
+
+#[tokio::test]
+async fn unstakes_grants_beyond_a_lowered_grantor_limit() {
+    let (mut env, minted) = funded_env(3).await;
+    let authority = env.platform_authority.pubkey();
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    grant(&mut env, 2, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.send_as_authority(instructions::update_grant_limits(
+        &authority,
+        1,
+        DEFAULT_MAX_GRANTORS_HISTORY,
+        DEFAULT_MIN_GRANT_RATIO,
+        DEFAULT_MAX_GRANT_RATIO,
+    ))
+    .await
+    .unwrap();
+    env.warp(ROUND_DURATION as i64 + 1);
+
+    env.unstake(0).await.unwrap();
+
+    assert_eq!(env.fctr_balance(1).await, minted);
+    assert_eq!(env.fctr_balance(2).await, minted);
+}
+
+#[tokio::test]
+async fn claims_granted_tokens_back() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+
+    env.send_as_user(
+        1,
+        instructions::claim_tokens(&grantor, &grantor, &confidant),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(env.fctr_balance(1).await, minted);
+    let receipt = env.receipt(0).await;
+    assert!(receipt.grantors.is_empty());
+    assert_eq!(receipt.amount_deposited, minted);
+    assert_eq!(env.user_stats(1).await.fctr_claimed, minted / 2);
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_staked, minted);
+    assert_eq!(platform.total_fctr_granted, 0);
+
+    let result = env
+        .send_as_user(
+            1,
+            instructions::claim_tokens(&grantor, &grantor, &confidant),
+        )
+        .await;
+    assert_custom_error(result, CustomErrors::GrantorNotFound);
+}
+
+#[tokio::test]
+async fn puts_grantors_on_cooldown() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    env.send_as_user(
+        1,
+        instructions::claim_tokens(&grantor, &grantor, &confidant),
+    )
+    .await
+    .unwrap();
+
+    let result = grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS).await;
+
+    assert_custom_error(result, CustomErrors::GrantCooldown);
+}
+
+#[tokio::test]
+async fn unstaking_lifts_only_expired_cooldowns() {
+    let (mut env, minted) = funded_env(3).await;
+    let confidant = env.user(0);
+    let expired = pda::user(&env.user(1));
+    let recent = pda::user(&env.user(2));
+    let address = pda::grant_history(&confidant);
+    let mut history: GrantHistory = env.account(address).await;
+    history.push(GrantorHistoryRecord {
+        grant_ts: (env.now as u64) - 30 * ROUND_DURATION - 1,
+        grantor: expired,
+    });
+    history.push(GrantorHistoryRecord {
+        grant_ts: env.now as u64,
+        grantor: recent,
+    });
+    env.set_program_account(
+        address,
+        &history,
+        8 + GrantHistory::space(history.capacity()),
+    );
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.warp(ROUND_DURATION as i64 + 1);
+
+    env.unstake(0).await.unwrap();
+
+    let history: GrantHistory = env.account(address).await;
+    assert!(!history.contains(&expired));
+    assert!(history.contains(&recent));
+    grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    let result = grant(&mut env, 2, 0, minted / 4, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::GrantCooldown);
+}
+
+#[tokio::test]
+async fn revokes_pending_grants() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    let apr = env.receipt(1).await.apr;
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    // The APR is restored however the grantor's balance changed since.
+    let bought = env.buy(1, 1_000).await;
+
+    env.send_as_user(1, instructions::revoke_grant(&grantor, &confidant))
+        .await
+        .unwrap();
+
+    assert_eq!(env.fctr_balance(1).await, minted + bought);
+    assert!((env.receipt(1).await.apr - apr).abs() < 1e-12);
+    assert!(env.receipt(0).await.next_round_grantors.is_empty());
+    let history: GrantHistory = env.account(pda::grant_history(&confidant)).await;
+    assert!(!history.contains(&pda::user(&grantor)));
+    let reputation: Reputation = env.account(pda::reputation(&confidant)).await;
+    assert_eq!(reputation.grants_received, 0);
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_pending, 0);
+    assert_eq!(platform.total_fctr_granted, 0);
+
+    // Once staked, a grant can only be claimed.
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    let result = env
+        .send_as_user(1, instructions::revoke_grant(&grantor, &confidant))
+        .await;
+    assert_custom_error(result, CustomErrors::GrantorNotFound);
+}
+
+/// BCDEV the first and second user get for reclaiming their grants to the third in `order`.
+async fn reclaimed_rewards(order: [usize; 2]) -> [u64; 2] {
+    let (mut env, minted) = funded_env(3).await;
+    let confidant = env.user(0);
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    grant(&mut env, 2, 0, minted / 4, 2_000).await.unwrap();
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.warp(2 * ROUND_DURATION as i64 + 1);
+
+    for index in order {
+        let grantor = env.user(index);
+        env.send_as_user(index, instructions::reclaim_grant(&grantor, &confidant))
+            .await
+            .unwrap();
+    }
+    [env.bcdev_balance(1).await, env.bcdev_balance(2).await]
+}
+
+#[tokio::test]
+async fn reclaimed_rewards_dont_depend_on_the_order() {
+    let rewards = reclaimed_rewards([1, 2]).await;
+
+    assert!(rewards.iter().all(|&reward| reward > 0));
+    assert_eq!(reclaimed_rewards([2, 1]).await, rewards);
+}
+
+#[tokio::test]
+async fn revoking_a_grant_lifts_its_cooldown() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    let result = grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TokensAlreadyGranted);
+
+    env.send_as_user(1, instructions::revoke_grant(&grantor, &confidant))
+        .await
+        .unwrap();
+
+    grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    let history: GrantHistory = env.account(pda::grant_history(&confidant)).await;
+    assert_eq!(history.iter().count(), 1);
+}
+
+#[tokio::test]
+async fn reclaims_grants_after_the_grace_period() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+
+    env.warp(2 * ROUND_DURATION as i64);
+    let result = env
+        .send_as_user(1, instructions::reclaim_grant(&grantor, &confidant))
+        .await;
+    assert_custom_error(result, CustomErrors::ReclaimTooEarly);
+
+    env.warp(1);
+    // A paused platform doesn't keep grantors from their tokens.
+    env.update_platform(|platform| platform.is_paused = true)
+        .await;
+    env.send_as_user(1, instructions::reclaim_grant(&grantor, &confidant))
+        .await
+        .unwrap();
+
+    assert_eq!(env.fctr_balance(1).await, minted);
+    let reward = env.bcdev_balance(1).await;
+    assert!(reward > 0);
+    assert_eq!(env.user_stats(1).await.bcdev_earned, reward);
+    let receipt = env.receipt(0).await;
+    assert!(receipt.grantors.is_empty());
+    assert_eq!(receipt.amount_deposited, minted);
+    let reputation: Reputation = env.account(pda::reputation(&confidant)).await;
+    assert_eq!(reputation.missed_unstakes, 1);
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_staked, minted);
+    assert_eq!(platform.total_bcdev_rewards, reward);
+
+    // The confidant can still unstake his own tokens.
+    env.unstake(0).await.unwrap();
+    assert_eq!(env.fctr_balance(0).await, minted);
+}
+
+#[tokio::test]
+async fn rejects_grants_outside_the_grant_program() {
+    let mut genesis = Genesis::new().with_users(1);
+    genesis.register_user(false);
+    let mut env = genesis.start().await;
+    let minted = env.buy(0, 1_000_000).await;
+    env.buy(1, 1_000_000).await;
+    env.trust(0, &[1]).await;
+    env.trust(1, &[0]).await;
+
+    let result = grant(&mut env, 0, 1, minted / 2, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::GrantProgramError);
+    let result = grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::GrantProgramError);
+}
+
+#[tokio::test]
+async fn rejects_direct_grants_without_the_confidants_consent() {
+    let mut env = Genesis::new().with_users(2).start().await;
+    let minted = env.buy(0, 1_000_000).await;
+    env.buy(1, 1_000_000).await;
+
+    // An open trust list doesn't let anyone push grants onto the receipt.
+    let result = grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TrustListViolation);
+
+    env.trust(0, &[1]).await;
+    for amount in [0, minted] {
+        let result = grant(&mut env, 1, 0, amount, GRANTOR_SHARE_BPS).await;
+        assert_custom_error(result, CustomErrors::TokenGrantError);
+    }
+    grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejects_grants_out_of_the_platform_limits() {
+    let (mut env, minted) = funded_env(3).await;
+    // The grantor's own FCTR has to stay within the allowed ratio to the confidant's.
+    env.buy(2, 3_000_000).await;
+
+    let result = grant(&mut env, 2, 0, minted / 2, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TokenGrantError);
+
+    grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+    let result = grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TokensAlreadyGranted);
+    env.update_platform(|platform| platform.max_active_grantors = 1)
+        .await;
+    let result = grant(&mut env, 2, 0, minted / 4, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::GrantorsCountLimit);
+    env.update_platform(|platform| platform.max_active_grantors = DEFAULT_MAX_ACTIVE_GRANTORS)
+        .await;
+
+    for grantor_share_bps in [
+        DEFAULT_MIN_GRANTOR_SHARE_BPS - 1,
+        DEFAULT_MAX_GRANTOR_SHARE_BPS + 1,
+    ] {
+        let result = grant(&mut env, 0, 1, minted / 4, grantor_share_bps).await;
+        assert_custom_error(result, CustomErrors::InvalidGrantorShare);
+    }
+}
+
+#[tokio::test]
+async fn rejects_grants_to_receipts_below_the_grant_limits() {
+    let (mut env, minted) = funded_env(2).await;
+    let authority = env.platform_authority.pubkey();
+    env.send_as_authority(instructions::update_grant_limits(
+        &authority,
+        DEFAULT_MAX_ACTIVE_GRANTORS + 2,
+        DEFAULT_MAX_GRANTORS_HISTORY,
+        DEFAULT_MIN_GRANT_RATIO,
+        DEFAULT_MAX_GRANT_RATIO,
+    ))
+    .await
+    .unwrap();
+
+    let result = grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS).await;
+
+    assert_custom_error(result, CustomErrors::ReceiptNotResized);
+}
+
+#[tokio::test]
+async fn rejects_grants_while_paused() {
+    let (mut env, minted) = funded_env(2).await;
+    env.update_platform(|platform| platform.is_paused = true)
+        .await;
+
+    let result = grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS).await;
+
+    assert_custom_error(result, CustomErrors::PlatformPaused);
+}
+
+#[tokio::test]
+async fn enforces_trust_lists() {
+    let (mut env, minted) = funded_env(3).await;
+    let confidant = env.user(0);
+    let blocked = env.user(1);
+    let allowed = env.user(2);
+
+    env.send_as_user(
+        0,
+        instructions::update_trust_list(
+            &confidant,
+            TrustListMode::Open,
+            vec![TrustEntry {
+                user: blocked,
+                allowed: false,
+            }],
+        ),
+    )
+    .await
+    .unwrap();
+    let result = grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TrustListViolation);
+
+    env.send_as_user(
+        0,
+        instructions::update_trust_list(
+            &confidant,
+            TrustListMode::AllowListOnly,
+            vec![TrustEntry {
+                user: allowed,
+                allowed: true,
+            }],
+        ),
+    )
+    .await
+    .unwrap();
+    let result = grant(&mut env, 1, 0, minted / 4, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TrustListViolation);
+    grant(&mut env, 2, 0, minted / 4, GRANTOR_SHARE_BPS)
+        .await
+        .unwrap();
+
+    // The grantor's list has to permit the confidant too.
+    let result = grant(&mut env, 0, 1, minted / 4, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TrustListViolation);
+}
+
+#[tokio::test]
+async fn rejects_oversized_trust_lists() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    let entries = (0..=MAX_TRUST_LIST_ENTRIES)
+        .map(|_| TrustEntry {
+            user: solana_sdk::pubkey::Pubkey::new_unique(),
+            allowed: true,
+        })
+        .collect();
+
+    let result = env
+        .send_as_user(
+            0,
+            instructions::update_trust_list(&user, TrustListMode::AllowListOnly, entries),
+        )
+        .await;
+
+    assert_custom_error(result, CustomErrors::TrustListFull);
+}
+
+/// Offers are created by `offer_grant`, which needs the compiled program, so the offer and
+/// the tokens it holds are seeded here the way `offer_grant` leaves them.
+async fn seed_offer(env: &mut TestEnv, grantor: usize, confidant: usize, amount: u64) {
+    let grantor = env.user(grantor);
+    let confidant = env.user(confidant);
+    let platform = env.platform().await;
+    env.set_program_account(
+        pda::grant_offer(&grantor, &confidant),
+        &GrantOffer {
+            bump: bump(&[b"grant_offer", grantor.as_ref(), confidant.as_ref()]),
+            amount,
+            grantor_share_bps: GRANTOR_SHARE_BPS,
+            created_round: platform.round_index,
+            expires_round: platform.round_index + platform.grant_offer_ttl_rounds,
+            grantor,
+            confidant,
+        },
+        8 + GrantOffer::SPACE,
+    );
+    let balance = env.token_amount(pda::fctr_vault(&grantor)).await;
+    env.set_token_amount(pda::fctr_vault(&grantor), balance - amount)
+        .await;
+    let held = env.token_amount(pda::fctr_token_vault()).await;
+    env.set_token_amount(pda::fctr_token_vault(), held + amount)
+        .await;
+    env.update_platform(|platform| platform.total_fctr_offered += amount)
+        .await;
+}
+
+#[tokio::test]
+async fn open_trust_lists_take_only_offers_from_users_they_dont_allow() {
+    let mut env = Genesis::new().with_users(2).start().await;
+    let minted = env.buy(0, 1_000_000).await;
+    env.buy(1, 1_000_000).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+
+    let result = grant(&mut env, 1, 0, minted / 2, GRANTOR_SHARE_BPS).await;
+    assert_custom_error(result, CustomErrors::TrustListViolation);
+
+    seed_offer(&mut env, 1, 0, minted / 2).await;
+    env.send_as_user(0, instructions::accept_grant(&confidant, &grantor))
+        .await
+        .unwrap();
+    assert_eq!(env.receipt(0).await.next_round_grantors.len(), 1);
+}
+
+#[tokio::test]
+async fn accepts_grant_offers() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    seed_offer(&mut env, 1, 0, minted / 2).await;
+    let lamports = env.lamports(grantor).await;
+
+    env.send_as_user(0, instructions::accept_grant(&confidant, &grantor))
+        .await
+        .unwrap();
+
+    assert!(!env.exists(pda::grant_offer(&grantor, &confidant)).await);
+    assert!(env.lamports(grantor).await > lamports);
+    let receipt = env.receipt(0).await;
+    assert_eq!(receipt.next_round_grantors.len(), 1);
+    assert_eq!(receipt.next_round_grantors[0].amount, minted / 2);
+    assert_eq!(
+        receipt.next_round_grantors[0].grantor_share_bps,
+        GRANTOR_SHARE_BPS
+    );
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_offered, 0);
+    assert_eq!(platform.total_fctr_pending, minted / 2);
+}
+
+#[tokio::test]
+async fn rejects_expired_grant_offers() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    seed_offer(&mut env, 1, 0, minted / 2).await;
+    env.update_platform(|platform| platform.round_index += DEFAULT_GRANT_OFFER_TTL_ROUNDS + 1)
+        .await;
+
+    let result = env
+        .send_as_user(0, instructions::accept_grant(&confidant, &grantor))
+        .await;
+
+    assert_custom_error(result, CustomErrors::GrantOfferExpired);
+}
+
+#[tokio::test]
+async fn confidant_rejects_grant_offers() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    seed_offer(&mut env, 1, 0, minted / 2).await;
+
+    env.send_as_user(
+        0,
+        instructions::reject_grant(&grantor, &confidant, &confidant),
+    )
+    .await
+    .unwrap();
+
+    assert!(!env.exists(pda::grant_offer(&grantor, &confidant)).await);
+    assert_eq!(env.fctr_balance(1).await, minted);
+    assert_eq!(env.platform().await.total_fctr_offered, 0);
+}
+
+#[tokio::test]
+async fn grantor_takes_back_only_expired_offers() {
+    let (mut env, minted) = funded_env(2).await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    seed_offer(&mut env, 1, 0, minted / 2).await;
+
+    let result = env
+        .send_as_user(
+            1,
+            instructions::reject_grant(&grantor, &confidant, &grantor),
+        )
+        .await;
+    assert_custom_error(result, CustomErrors::UnauthorizedSigner);
+
+    env.update_platform(|platform| platform.round_index += DEFAULT_GRANT_OFFER_TTL_ROUNDS + 1)
+        .await;
+    env.send_as_user(
+        1,
+        instructions::reject_grant(&grantor, &confidant, &grantor),
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.fctr_balance(1).await, minted);
+}
@@ -0,0 +1,235 @@
+//! Migration of the accounts the first version of the program left behind.
+//!
+//! The legacy accounts are made by cutting the current `User` and `Platform` down to their
+//! original fields and by rewriting the receipt in its original layout. Migrating grows and
+//! creates accounts, so these tests only run on the compiled program with `test-bpf`.
+#![cfg(feature = "test-bpf")]
+
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::{AccountSerialize, AnchorSerialize, Discriminator};
+use common::*;
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::signature::Signer;
+use solana_token_stacking::errors::CustomErrors;
+use solana_token_stacking::state::{
+    GrantHistory, LegacyGrantorRecord, LegacyReceipt, Platform, Receipt, TrustList, User,
+    DEFAULT_MAX_ACTIVE_GRANTORS, LEGACY_GRANTOR_SHARE_BPS, LEGACY_PLATFORM_SPACE,
+    LEGACY_USER_SPACE,
+};
+use solana_token_stacking_client::{instructions, pda};
+
+fn set_data(env: &mut TestEnv, address: Pubkey, data: Vec<u8>) {
+    let mut account = AccountSharedData::new(
+        Rent::default().minimum_balance(data.len()),
+        data.len(),
+        &solana_token_stacking::ID,
+    );
+    account.set_data(data);
+    env.context.set_account(&address, &account);
+}
+
+fn truncated<T: AccountSerialize>(account: &T, len: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.truncate(len);
+    data
+}
+
+/// Turns the platform back into the first version's, which kept no totals.
+async fn downgrade_platform(env: &mut TestEnv) {
+    let platform = env.platform().await;
+    let data = truncated(&platform, 8 + LEGACY_PLATFORM_SPACE);
+    set_data(env, pda::platform(), data);
+}
+
+/// Turns a user back into the first version's, with the grant history in the receipt and
+/// without the accounts `register_user` creates since.
+async fn downgrade_user(env: &mut TestEnv, index: usize) {
+    let authority = env.user(index);
+    let user: User = env.account(pda::user(&authority)).await;
+    set_data(
+        env,
+        pda::user(&authority),
+        truncated(&user, 8 + LEGACY_USER_SPACE),
+    );
+
+    let receipt = env.receipt(index).await;
+    let history: GrantHistory = env.account(pda::grant_history(&authority)).await;
+    let legacy_record = |g: &solana_token_stacking::state::GrantorRecord| LegacyGrantorRecord {
+        amount: g.amount,
+        grant_duration: g.grant_duration,
+        grantor: g.grantor,
+    };
+    let legacy = LegacyReceipt {
+        is_valid: receipt.is_valid,
+        stake_duration: receipt.stake_duration,
+        stake_ts: receipt.stake_ts,
+        round_ends: receipt.round_ends,
+        amount_deposited: receipt.amount_deposited,
+        apr: receipt.apr,
+        grantors: receipt.grantors.iter().map(legacy_record).collect(),
+        grantors_history: history.iter().copied().collect(),
+        next_round_grantors: receipt
+            .next_round_grantors
+            .iter()
+            .map(legacy_record)
+            .collect(),
+        authority,
+    };
+    let mut data = Receipt::discriminator().to_vec();
+    legacy.serialize(&mut data).unwrap();
+    data.resize(8 + LegacyReceipt::SPACE, 0);
+    set_data(env, pda::receipt(&authority), data);
+
+    for address in [
+        pda::grant_history(&authority),
+        pda::reputation(&authority),
+        pda::user_stats(&authority),
+        pda::trust_list(&authority),
+    ] {
+        env.context
+            .set_account(&address, &AccountSharedData::default());
+    }
+}
+
+/// A staker holding a grant, so every total the migration rebuilds is set.
+async fn staked_env() -> TestEnv {
+    let mut env = Genesis::new().with_users(2).start().await;
+    let grantor = env.user(1);
+    let confidant = env.user(0);
+    env.buy(0, 1_000_000).await;
+    let minted = env.buy(1, 1_000_000).await;
+    env.trust(0, &[1]).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.send_as_user(
+        1,
+        instructions::grant_tokens(&grantor, &confidant, minted / 2, LEGACY_GRANTOR_SHARE_BPS),
+    )
+    .await
+    .unwrap();
+    env
+}
+
+#[tokio::test]
+async fn migrates_the_platform() {
+    let mut env = staked_env().await;
+    let authority = env.platform_authority.pubkey();
+    let before = env.platform().await;
+    downgrade_platform(&mut env).await;
+
+    let result = env
+        .send_as_user(0, instructions::migrate_platform(&env.user(0)))
+        .await;
+    assert_custom_error(result, CustomErrors::UnauthorizedSigner);
+    env.send_as_authority(instructions::migrate_platform(&authority))
+        .await
+        .unwrap();
+
+    let platform: Platform = env.platform().await;
+    assert_eq!(platform.round_index, 1);
+    assert_eq!(platform.round_start, before.round_start);
+    assert_eq!(platform.max_active_grantors, DEFAULT_MAX_ACTIVE_GRANTORS);
+    assert_eq!(platform.reclaim_grace_period, ROUND_DURATION);
+    assert_eq!(platform.total_sol_in, before.total_sol_in);
+    // The stakes are only counted again as their users are migrated.
+    assert_eq!(platform.total_fctr_staked, 0);
+    let result = env
+        .send_as_authority(instructions::migrate_platform(&authority))
+        .await;
+    assert_custom_error(result, CustomErrors::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_users_and_rebuilds_the_totals() {
+    let mut env = staked_env().await;
+    let platform_authority = env.platform_authority.pubkey();
+    let staker = env.user(0);
+    let grantor = env.user(1);
+    let before = env.platform().await;
+    let receipt_before = env.receipt(0).await;
+    downgrade_platform(&mut env).await;
+    downgrade_user(&mut env, 0).await;
+    downgrade_user(&mut env, 1).await;
+
+    // Users wait for the platform.
+    let result = env
+        .send_as_user(1, instructions::migrate_user(&grantor, &staker))
+        .await;
+    assert!(result.is_err());
+    env.send_as_authority(instructions::migrate_platform(&platform_authority))
+        .await
+        .unwrap();
+    let lamports = env.lamports(staker).await;
+    // Anyone can pay for the migration of a user.
+    env.send_as_user(1, instructions::migrate_user(&grantor, &staker))
+        .await
+        .unwrap();
+    env.send_as_user(1, instructions::migrate_user(&grantor, &grantor))
+        .await
+        .unwrap();
+
+    let platform = env.platform().await;
+    assert_eq!(platform.active_stakers, before.active_stakers);
+    assert_eq!(platform.total_fctr_staked, before.total_fctr_staked);
+    assert_eq!(platform.total_fctr_granted, before.total_fctr_granted);
+    assert_eq!(platform.total_fctr_pending, before.total_fctr_pending);
+    let receipt = env.receipt(0).await;
+    assert_eq!(receipt.amount_deposited, receipt_before.amount_deposited);
+    assert_eq!(receipt.grantors.len(), 1);
+    assert_eq!(
+        receipt.grantors[0].amount,
+        receipt_before.grantors[0].amount
+    );
+    assert_eq!(
+        receipt.grantors[0].grantor_share_bps,
+        LEGACY_GRANTOR_SHARE_BPS
+    );
+    // The receipt shrinks and its owner gets the rent back.
+    let receipt_len = 8 + Receipt::space(DEFAULT_MAX_ACTIVE_GRANTORS as usize);
+    let rent = Rent::default();
+    assert_eq!(
+        env.lamports(pda::receipt(&staker)).await,
+        rent.minimum_balance(receipt_len)
+    );
+    assert_eq!(
+        env.lamports(staker).await - lamports,
+        rent.minimum_balance(8 + LegacyReceipt::SPACE) - rent.minimum_balance(receipt_len)
+    );
+    let history: GrantHistory = env.account(pda::grant_history(&staker)).await;
+    assert!(history.contains(&pda::user(&grantor)));
+    let trust_list: TrustList = env.account(pda::trust_list(&staker)).await;
+    assert_eq!(trust_list.authority, staker);
+    let user: User = env.account(pda::user(&staker)).await;
+    assert_eq!(user.operator, None);
+
+    let result = env
+        .send_as_user(0, instructions::migrate_user(&staker, &staker))
+        .await;
+    assert_custom_error(result, CustomErrors::AccountAlreadyMigrated);
+
+    env.warp(ROUND_DURATION as i64 + 1);
+    env.unstake(0).await.unwrap();
+    assert_eq!(env.platform().await.total_fctr_staked, 0);
+}
+
+#[tokio::test]
+async fn creates_the_missing_accounts_of_a_user() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    env.context
+        .set_account(&pda::trust_list(&user), &AccountSharedData::default());
+
+    env.send_as_user(0, instructions::migrate_user(&user, &user))
+        .await
+        .unwrap();
+
+    let trust_list: TrustList = env.account(pda::trust_list(&user)).await;
+    assert_eq!(trust_list.authority, user);
+    let result = env
+        .send_as_user(0, instructions::migrate_user(&user, &user))
+        .await;
+    assert_custom_error(result, CustomErrors::AccountAlreadyMigrated);
+}
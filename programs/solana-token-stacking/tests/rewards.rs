@@ -0,0 +1,161 @@
+use proptest::prelude::*;
+use solana_token_stacking::rewards::{self, Grant};
+use solana_token_stacking::state::BPS_DENOMINATOR;
+
+const MAX_GRANTS: usize = 10;
+const MAX_ROUND_DURATION: u64 = 10 * 365 * 24 * 60 * 60;
+
+#[derive(Clone, Debug)]
+struct Stake {
+    deposited: u64,
+    apr: f64,
+    stake_duration: u64,
+    grants: Vec<Grant>,
+    round_duration: u64,
+}
+
+impl Stake {
+    fn reward(&self) -> u64 {
+        rewards::total_reward(
+            self.deposited,
+            self.apr,
+            self.stake_duration,
+            &self.grants,
+            self.round_duration,
+        )
+    }
+
+    /// The same stake held `delta` seconds longer, along with every grant in it.
+    fn extended(&self, delta: u64) -> Stake {
+        let extend = |duration: u64| (duration + delta).min(self.round_duration);
+        Stake {
+            stake_duration: extend(self.stake_duration),
+            grants: self
+                .grants
+                .iter()
+                .map(|grant| Grant {
+                    duration: extend(grant.duration),
+                    ..*grant
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Grants are part of the deposit and never staked for longer than the stake itself.
+fn stakes() -> impl Strategy<Value = Stake> {
+    (
+        any::<u64>(),
+        0.0..1.0f64,
+        1..=MAX_ROUND_DURATION,
+        0..=MAX_GRANTS,
+    )
+        .prop_flat_map(|(deposited, apr, round_duration, grants)| {
+            (
+                Just((deposited, apr, round_duration, grants)),
+                0..=round_duration,
+            )
+        })
+        .prop_flat_map(
+            |((deposited, apr, round_duration, grants), stake_duration)| {
+                let grant = (
+                    0..=deposited / grants.max(1) as u64,
+                    0..=stake_duration,
+                    0..=BPS_DENOMINATOR,
+                )
+                    .prop_map(|(amount, duration, grantor_share_bps)| Grant {
+                        amount,
+                        duration,
+                        grantor_share_bps,
+                    });
+                proptest::collection::vec(grant, grants).prop_map(move |grants| Stake {
+                    deposited,
+                    apr,
+                    stake_duration,
+                    grants,
+                    round_duration,
+                })
+            },
+        )
+}
+
+fn grants() -> impl Strategy<Value = Vec<Grant>> {
+    let grant = (
+        1..=u64::MAX / MAX_GRANTS as u64,
+        any::<u64>(),
+        0..=BPS_DENOMINATOR,
+    )
+        .prop_map(|(amount, duration, grantor_share_bps)| Grant {
+            amount,
+            duration,
+            grantor_share_bps,
+        });
+    proptest::collection::vec(grant, 1..=MAX_GRANTS)
+}
+
+proptest! {
+    #[test]
+    fn reward_math_never_overflows(stake in stakes()) {
+        let weighted =
+            rewards::time_weighted_deposit(stake.deposited, stake.stake_duration, &stake.grants);
+        prop_assert!(weighted <= stake.deposited);
+
+        let total_reward = stake.reward();
+        let split = rewards::split_reward(total_reward, &stake.grants);
+        prop_assert!(split.total() <= total_reward);
+    }
+
+    #[test]
+    fn longer_stakes_never_earn_less(stake in stakes(), delta in 0..=MAX_ROUND_DURATION) {
+        prop_assert!(stake.extended(delta).reward() >= stake.reward());
+    }
+
+    #[test]
+    fn grantor_shares_stay_within_the_grantor_pool(
+        total_reward in any::<u64>(),
+        grants in grants(),
+    ) {
+        let pool = rewards::grantor_pool(total_reward, &grants);
+        let split = rewards::split_reward(total_reward, &grants);
+
+        prop_assert!(split.grantor_rewards.iter().sum::<u64>() <= pool);
+        prop_assert!(split.staker_reward + pool <= total_reward);
+        // Each share is rounded down, which loses less than a unit per share.
+        prop_assert!(total_reward - split.total() <= grants.len() as u64 + 1);
+    }
+
+    #[test]
+    fn stakes_without_grants_keep_the_whole_reward(total_reward in any::<u64>()) {
+        let split = rewards::split_reward(total_reward, &[]);
+
+        prop_assert_eq!(split.staker_reward, total_reward);
+        prop_assert_eq!(rewards::grantor_pool(total_reward, &[]), 0);
+    }
+
+    #[test]
+    fn grantor_aprs_stay_finite(
+        apr in 0.0..1.0f64,
+        balance in any::<u64>(),
+        part_bps in 0..=BPS_DENOMINATOR,
+    ) {
+        let amount = (balance as u128 * part_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let grantor_apr = rewards::grantor_apr(apr, balance, amount);
+
+        prop_assert!(grantor_apr.is_finite());
+        prop_assert!(grantor_apr >= apr);
+    }
+
+    #[test]
+    fn revoking_a_grant_restores_the_grantor_apr(
+        apr in 0.0..1.0f64,
+        balance in 1..u64::MAX,
+        part_bps in 0..=BPS_DENOMINATOR,
+    ) {
+        let amount = (balance as u128 * part_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let grantor_apr = rewards::grantor_apr(apr, balance, amount);
+        let restored = rewards::revoked_grantor_apr(grantor_apr, balance, amount);
+
+        prop_assert!((restored - apr).abs() <= apr * 1e-9);
+    }
+}
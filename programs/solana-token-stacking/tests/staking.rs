@@ -0,0 +1,360 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_token_stacking::errors::CustomErrors;
+use solana_token_stacking::state::{
+    GrantorRecord, Position, Receipt, Reputation, RewardProjection, Snapshot, User, UserSummary,
+    VotingWeight,
+};
+use solana_token_stacking_client::{instructions, pda};
+
+const BASE_APR: f64 = 0.01;
+
+#[tokio::test]
+async fn stakes_and_unstakes_across_rounds() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let minted = env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    env.warp(40);
+
+    env.stake(0).await.unwrap();
+
+    let receipt = env.receipt(0).await;
+    assert!(receipt.is_valid);
+    assert_eq!(receipt.amount_deposited, minted);
+    assert_eq!(receipt.stake_ts, env.now as u64);
+    assert_eq!(receipt.round_ends, START_TIME as u64 + ROUND_DURATION);
+    assert_eq!(receipt.stake_duration, ROUND_DURATION - 40);
+    assert_eq!(env.fctr_balance(0).await, 0);
+    assert_eq!(env.token_amount(pda::fctr_token_vault()).await, minted);
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_staked, minted);
+    assert_eq!(platform.active_stakers, 1);
+
+    // The next round starts while the stake is still held.
+    env.warp(ROUND_DURATION as i64);
+    env.start_round().await;
+    env.unstake(0).await.unwrap();
+
+    let reward = (minted as f64 * BASE_APR) as u64;
+    assert_eq!(env.fctr_balance(0).await, minted);
+    assert_eq!(env.bcdev_balance(0).await, reward);
+    assert!(!env.receipt(0).await.is_valid);
+    let stats = env.user_stats(0).await;
+    assert_eq!(stats.rounds_participated, 1);
+    assert_eq!(stats.bcdev_earned, reward);
+    let platform = env.platform().await;
+    assert_eq!(platform.total_fctr_staked, 0);
+    assert_eq!(platform.active_stakers, 0);
+    assert_eq!(platform.bcdev_token_total_amount, reward);
+    assert_eq!(platform.total_bcdev_rewards, reward);
+
+    // The same receipt is staked again in the new round.
+    env.stake(0).await.unwrap();
+    assert_eq!(env.receipt(0).await.stake_duration, ROUND_DURATION);
+}
+
+#[tokio::test]
+async fn records_late_unstakes() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+
+    env.warp(2 * ROUND_DURATION as i64 + 1);
+    env.unstake(0).await.unwrap();
+
+    let reputation: Reputation = env.account(pda::reputation(&user)).await;
+    assert_eq!(reputation.on_time_unstakes, 0);
+    assert_eq!(reputation.late_unstakes, 1);
+}
+
+#[tokio::test]
+async fn rejects_staking_outside_a_round() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    env.buy(0, 1_000_000).await;
+
+    assert_custom_error(env.stake(0).await, CustomErrors::NoActiveRound);
+
+    env.start_round().await;
+    env.warp(ROUND_DURATION as i64 + 1);
+    assert_custom_error(env.stake(0).await, CustomErrors::NoActiveRound);
+}
+
+#[tokio::test]
+async fn rejects_staking_twice() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+
+    assert_custom_error(env.stake(0).await, CustomErrors::InvalidReceipt);
+}
+
+#[tokio::test]
+async fn rejects_unstaking_during_the_round() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    env.buy(0, 1_000_000).await;
+
+    assert_custom_error(env.unstake(0).await, CustomErrors::InvalidReceipt);
+
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.warp(ROUND_DURATION as i64);
+    assert_custom_error(env.unstake(0).await, CustomErrors::RoundStillGoing);
+}
+
+#[tokio::test]
+async fn rejects_a_receipt_of_another_user() {
+    let mut env = Genesis::new().with_users(2).start().await;
+    env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    let other = env.user(1);
+    env.update_receipt(0, |receipt| receipt.authority = other)
+        .await;
+
+    assert_custom_error(env.stake(0).await, CustomErrors::InvalidReceiptAuthority);
+}
+
+#[tokio::test]
+async fn rejects_staking_while_paused() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    env.update_platform(|platform| platform.is_paused = true)
+        .await;
+
+    assert_custom_error(env.stake(0).await, CustomErrors::PlatformPaused);
+}
+
+#[tokio::test]
+async fn operator_stakes_and_unstakes_for_the_user() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    let minted = env.buy(0, 1_000_000).await;
+    let operator = Keypair::new();
+    env.send_as_user(
+        0,
+        instructions::set_operator(&user, Some(operator.pubkey())),
+    )
+    .await
+    .unwrap();
+    let user_account: User = env.account(pda::user(&user)).await;
+    assert_eq!(user_account.operator, Some(operator.pubkey()));
+    env.start_round().await;
+
+    env.send(instructions::stake(&user, &operator.pubkey()), &[&operator])
+        .await
+        .unwrap();
+    env.warp(ROUND_DURATION as i64 + 1);
+    env.send(
+        instructions::unstake(&user, &operator.pubkey(), &[]),
+        &[&operator],
+    )
+    .await
+    .unwrap();
+
+    // Everything stays in the user's vaults.
+    assert_eq!(env.fctr_balance(0).await, minted);
+    assert!(env.bcdev_balance(0).await > 0);
+}
+
+#[tokio::test]
+async fn rejects_signers_that_are_not_the_operator() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    let stranger = Keypair::new();
+
+    let result = env
+        .send(instructions::stake(&user, &stranger.pubkey()), &[&stranger])
+        .await;
+    assert_custom_error(result, CustomErrors::UnauthorizedSigner);
+
+    env.send_as_user(
+        0,
+        instructions::set_operator(&user, Some(stranger.pubkey())),
+    )
+    .await
+    .unwrap();
+    env.send_as_user(0, instructions::set_operator(&user, None))
+        .await
+        .unwrap();
+    let result = env
+        .send(instructions::stake(&user, &stranger.pubkey()), &[&stranger])
+        .await;
+    assert_custom_error(result, CustomErrors::UnauthorizedSigner);
+}
+
+/// Positions are created by `open_position`, which needs the compiled program, so the
+/// position in the state `open_position` leaves it in is seeded here.
+async fn seed_position(env: &mut TestEnv, index: usize, amount: u64, lock_rounds: u8) -> Position {
+    let authority = env.user(index);
+    let platform = env.platform().await;
+    let round_ends = platform.round_start + platform.round_duration;
+    let position = Position {
+        bump: bump(&[b"position", authority.as_ref(), &0u64.to_le_bytes()]),
+        id: 0,
+        lock_rounds,
+        stake_ts: env.now as u64,
+        stake_duration: round_ends - env.now as u64,
+        round_ends,
+        unlock_ts: round_ends + lock_rounds as u64 * platform.round_duration,
+        amount_deposited: amount,
+        apr: BASE_APR,
+        authority,
+    };
+    env.set_program_account(pda::position(&authority, 0), &position, 8 + Position::SPACE);
+    let mut user: User = env.account(pda::user(&authority)).await;
+    user.user_fctr_amount -= amount;
+    user.next_position_id = 1;
+    env.set_program_account(pda::user(&authority), &user, 8 + User::SPACE);
+    let vault = env.fctr_balance(index).await;
+    env.set_token_amount(pda::fctr_vault(&authority), vault - amount)
+        .await;
+    let platform_vault = env.token_amount(pda::fctr_token_vault()).await;
+    env.set_token_amount(pda::fctr_token_vault(), platform_vault + amount)
+        .await;
+    env.update_platform(|platform| platform.total_fctr_staked += amount)
+        .await;
+    position
+}
+
+#[tokio::test]
+async fn closes_unlocked_positions() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    let minted = env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    let amount = minted / 2;
+    let position = seed_position(&mut env, 0, amount, 2).await;
+
+    env.set_time(position.unlock_ts as i64);
+    let result = env
+        .send_as_user(0, instructions::close_position(&user, 0))
+        .await;
+    assert_custom_error(result, CustomErrors::PositionLocked);
+
+    env.warp(1);
+    env.send_as_user(0, instructions::close_position(&user, 0))
+        .await
+        .unwrap();
+
+    // The opening round counts as a full round here, plus two locked rounds.
+    let reward = (amount as f64 * BASE_APR * 3.0) as u64;
+    assert!(!env.exists(pda::position(&user, 0)).await);
+    assert_eq!(env.fctr_balance(0).await, minted);
+    assert_eq!(env.bcdev_balance(0).await, reward);
+    let user_account: User = env.account(pda::user(&user)).await;
+    assert_eq!(user_account.user_fctr_amount, minted);
+    assert_eq!(env.platform().await.total_fctr_staked, 0);
+}
+
+#[tokio::test]
+async fn proves_snapshotted_voting_weight() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    // Snapshots are created by `snapshot`, which needs the compiled program.
+    let round_le = 1u64.to_le_bytes();
+    env.set_program_account(
+        pda::snapshot(1),
+        &Snapshot {
+            bump: bump(&[b"snapshot", &round_le]),
+            round_index: 1,
+            round_start: START_TIME as u64,
+            total_weight: 500,
+            stakers_count: 2,
+        },
+        8 + Snapshot::SPACE,
+    );
+    env.set_program_account(
+        pda::voting_weight(1, &user),
+        &VotingWeight {
+            bump: bump(&[b"voting_weight", &round_le, user.as_ref()]),
+            round_index: 1,
+            weight: 200,
+            authority: user,
+        },
+        8 + VotingWeight::SPACE,
+    );
+
+    env.send(instructions::prove_voting_weight(&user, 1), &[])
+        .await
+        .unwrap();
+
+    let result = env
+        .send(instructions::prove_voting_weight(&user, 2), &[])
+        .await;
+    assert_anchor_error(result, ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn projects_rewards_and_summarizes_users() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    env.buy(0, 1_000_000).await;
+
+    env.send(instructions::user_summary(&user), &[])
+        .await
+        .unwrap();
+
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.send(instructions::project_reward(&user), &[])
+        .await
+        .unwrap();
+    env.send(instructions::user_summary(&user), &[])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn summarizes_users_with_more_grantors_than_the_return_data_holds() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    // As many as the platform allows, more than the summary holds.
+    let grantors = RewardProjection::MAX_GRANTOR_RETURNS;
+    assert!(grantors > UserSummary::MAX_GRANTOR_RETURNS);
+    // An operator makes the summary as long as it gets.
+    env.send_as_user(
+        0,
+        instructions::set_operator(&user, Some(Keypair::new().pubkey())),
+    )
+    .await
+    .unwrap();
+    env.update_platform(|platform| platform.max_active_grantors = grantors as u32)
+        .await;
+    env.set_program_account(
+        pda::receipt(&user),
+        &Receipt {
+            is_valid: true,
+            stake_duration: ROUND_DURATION,
+            round_ends: START_TIME as u64 + ROUND_DURATION,
+            amount_deposited: 2_000 * grantors as u64,
+            apr: BASE_APR,
+            grantors: (0..grantors)
+                .map(|_| GrantorRecord {
+                    amount: 1_000,
+                    grant_duration: ROUND_DURATION,
+                    grantor_share_bps: 5_000,
+                    grantor: Keypair::new().pubkey(),
+                    grantor_balance: 2_000,
+                })
+                .collect(),
+            authority: user,
+            ..Default::default()
+        },
+        8 + Receipt::space(grantors),
+    );
+
+    env.send(instructions::project_reward(&user), &[])
+        .await
+        .unwrap();
+    env.send(instructions::user_summary(&user), &[])
+        .await
+        .unwrap();
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use anchor_lang::prelude::Rent;
+use common::*;
+use solana_sdk::signature::Signer;
+use solana_token_stacking::errors::CustomErrors;
+use solana_token_stacking::pricing;
+use solana_token_stacking_client::{instructions, pda};
+
+#[tokio::test]
+async fn buys_fctr() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    let vault_lamports = env.lamports(pda::sol_vault()).await;
+
+    let minted = env.buy(0, 1_000).await;
+
+    assert_eq!(minted, pricing::fctr_for_lamports(1_000).unwrap());
+    assert_eq!(env.fctr_balance(0).await, minted);
+    assert_eq!(env.lamports(pda::sol_vault()).await, vault_lamports + 1_000);
+    let user_account: solana_token_stacking::state::User = env.account(pda::user(&user)).await;
+    assert_eq!(user_account.user_fctr_amount, minted);
+    let stats = env.user_stats(0).await;
+    assert_eq!(stats.fctr_bought, minted);
+    assert_eq!(stats.sol_paid, REGISTRATION_PRICE + 1_000);
+    let platform = env.platform().await;
+    assert_eq!(platform.fctr_token_total_amount, minted);
+    assert_eq!(platform.total_sol_in, REGISTRATION_PRICE + 1_000);
+}
+
+#[tokio::test]
+async fn rejects_buying_less_than_the_minimum() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+
+    let result = env
+        .send_as_user(0, instructions::buy_tokens(&user, 0))
+        .await;
+
+    assert_custom_error(result, CustomErrors::InvalidBuyAmount);
+}
+
+#[tokio::test]
+async fn sells_all_fctr() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    let minted = env.buy(0, 1_000).await;
+    let lamports = env.lamports(user).await;
+
+    env.send_as_user(0, instructions::sell_fctr_tokens(&user))
+        .await
+        .unwrap();
+
+    let paid = pricing::lamports_for_fctr(minted);
+    assert_eq!(env.fctr_balance(0).await, 0);
+    assert_eq!(env.lamports(user).await, lamports + paid);
+    let stats = env.user_stats(0).await;
+    assert_eq!(stats.fctr_sold, minted);
+    assert_eq!(stats.sol_received, paid);
+    let platform = env.platform().await;
+    assert_eq!(platform.fctr_token_total_amount, 0);
+    assert_eq!(platform.total_sol_out, paid);
+}
+
+#[tokio::test]
+async fn sells_earned_bcdev() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    env.buy(0, 1_000_000).await;
+    env.start_round().await;
+    env.stake(0).await.unwrap();
+    env.warp(ROUND_DURATION as i64 + 1);
+    env.unstake(0).await.unwrap();
+    let earned = env.bcdev_balance(0).await;
+    assert!(earned > 0);
+    let lamports = env.lamports(user).await;
+
+    env.send_as_user(0, instructions::sell_bcdev_tokens(&user, earned / 2))
+        .await
+        .unwrap();
+
+    let paid = pricing::lamports_for_bcdev(earned / 2);
+    assert_eq!(env.bcdev_balance(0).await, earned - earned / 2);
+    assert_eq!(env.lamports(user).await, lamports + paid);
+    assert_eq!(env.user_stats(0).await.bcdev_sold, earned / 2);
+    assert_eq!(
+        env.platform().await.bcdev_token_total_amount,
+        earned - earned / 2
+    );
+}
+
+#[tokio::test]
+async fn adds_liquidity() {
+    let mut env = Genesis::new().start().await;
+    let authority = env.platform_authority.pubkey();
+    let vault_lamports = env.lamports(pda::sol_vault()).await;
+
+    env.send_as_authority(instructions::add_liquidity(&authority, 5_000))
+        .await
+        .unwrap();
+
+    assert_eq!(env.lamports(pda::sol_vault()).await, vault_lamports + 5_000);
+    assert_eq!(env.platform().await.total_sol_in, 5_000);
+}
+
+#[tokio::test]
+async fn withdraws_once_all_tokens_are_sold() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let authority = env.platform_authority.pubkey();
+    let user = env.user(0);
+    env.buy(0, 1_000).await;
+
+    let result = env
+        .send_as_authority(instructions::withdraw(&authority))
+        .await;
+    assert_custom_error(result, CustomErrors::WithdrawConditions);
+
+    env.send_as_user(0, instructions::sell_fctr_tokens(&user))
+        .await
+        .unwrap();
+    let vault_lamports = env.lamports(pda::sol_vault()).await;
+    env.send_as_authority(instructions::withdraw(&authority))
+        .await
+        .unwrap();
+
+    let rent = Rent::default().minimum_balance(0);
+    assert_eq!(env.lamports(pda::sol_vault()).await, rent);
+    let platform = env.platform().await;
+    assert_eq!(
+        platform.total_sol_out,
+        pricing::lamports_for_fctr(pricing::fctr_for_lamports(1_000).unwrap()) + vault_lamports
+            - rent
+    );
+}
+
+#[tokio::test]
+async fn withdraws_after_the_final_round_expires() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let authority = env.platform_authority.pubkey();
+    env.buy(0, 1_000).await;
+    env.send_as_authority(instructions::start_round(&authority, true))
+        .await
+        .unwrap();
+
+    env.warp(3 * ROUND_DURATION as i64);
+    let result = env
+        .send_as_authority(instructions::withdraw(&authority))
+        .await;
+    assert_custom_error(result, CustomErrors::WithdrawConditions);
+
+    env.warp(1);
+    env.send_as_authority(instructions::withdraw(&authority))
+        .await
+        .unwrap();
+    assert_eq!(
+        env.lamports(pda::sol_vault()).await,
+        Rent::default().minimum_balance(0)
+    );
+}
+
+#[tokio::test]
+async fn quotes_trades() {
+    let mut env = Genesis::new().start().await;
+
+    env.send(instructions::quote_buy(1_000), &[]).await.unwrap();
+    env.send(instructions::quote_sell_fctr(1_000_000), &[])
+        .await
+        .unwrap();
+    env.send(instructions::quote_sell_bcdev(1_000_000), &[])
+        .await
+        .unwrap();
+    let result = env.send(instructions::quote_buy(0), &[]).await;
+    assert_custom_error(result, CustomErrors::InvalidBuyAmount);
+    let result = env.send(instructions::quote_buy(u64::MAX), &[]).await;
+    assert_custom_error(result, CustomErrors::BuyAmountTooLarge);
+}
+
+#[tokio::test]
+async fn trading_stops_while_paused() {
+    let mut env = Genesis::new().with_users(1).start().await;
+    let user = env.user(0);
+    env.buy(0, 1_000).await;
+    env.update_platform(|platform| platform.is_paused = true)
+        .await;
+
+    let result = env
+        .send_as_user(0, instructions::buy_tokens(&user, 1_000))
+        .await;
+    assert_custom_error(result, CustomErrors::PlatformPaused);
+    let result = env
+        .send_as_user(0, instructions::sell_fctr_tokens(&user))
+        .await;
+    assert_custom_error(result, CustomErrors::PlatformPaused);
+    let result = env
+        .send_as_user(0, instructions::sell_bcdev_tokens(&user, 0))
+        .await;
+    assert_custom_error(result, CustomErrors::PlatformPaused);
+}
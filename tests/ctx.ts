@@ -118,6 +118,129 @@ export class Context {
         return await this.program.account.receipt.fetch(pda);
     }
 
+    async position(authority: PublicKey, id: number | BN): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("position"),
+                authority.toBuffer(),
+                new BN(id).toArrayLike(Buffer, "le", 8),
+            ],
+            this.program.programId
+        );
+    }
+
+    async positionAcc(authority: PublicKey, id: number | BN) {
+        const pda = await this.position(authority, id);
+        return await this.program.account.position.fetch(pda);
+    }
+
+    async snapshot(roundIndex: number | BN): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("snapshot"),
+                new BN(roundIndex).toArrayLike(Buffer, "le", 8),
+            ],
+            this.program.programId
+        );
+    }
+
+    async snapshotAcc(roundIndex: number | BN) {
+        const pda = await this.snapshot(roundIndex);
+        return await this.program.account.snapshot.fetch(pda);
+    }
+
+    async votingWeight(roundIndex: number | BN, authority: PublicKey): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("voting_weight"),
+                new BN(roundIndex).toArrayLike(Buffer, "le", 8),
+                authority.toBuffer(),
+            ],
+            this.program.programId
+        );
+    }
+
+    async votingWeightAcc(roundIndex: number | BN, authority: PublicKey) {
+        const pda = await this.votingWeight(roundIndex, authority);
+        return await this.program.account.votingWeight.fetch(pda);
+    }
+
+    async grantOffer(grantor: PublicKey, confidant: PublicKey): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("grant_offer"),
+                grantor.toBuffer(),
+                confidant.toBuffer(),
+            ],
+            this.program.programId
+        );
+    }
+
+    async grantOfferAcc(grantor: PublicKey, confidant: PublicKey) {
+        const pda = await this.grantOffer(grantor, confidant);
+        return await this.program.account.grantOffer.fetch(pda);
+    }
+
+    async grantHistory(authority: PublicKey): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("grant_history"),
+                authority.toBuffer(),
+            ],
+            this.program.programId
+        );
+    }
+
+    async grantHistoryAcc(authority: PublicKey) {
+        const pda = await this.grantHistory(authority);
+        return await this.program.account.grantHistory.fetch(pda);
+    }
+
+    async reputation(authority: PublicKey): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("reputation"),
+                authority.toBuffer(),
+            ],
+            this.program.programId
+        );
+    }
+
+    async reputationAcc(authority: PublicKey) {
+        const pda = await this.reputation(authority);
+        return await this.program.account.reputation.fetch(pda);
+    }
+
+    async userStats(authority: PublicKey): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("user_stats"),
+                authority.toBuffer(),
+            ],
+            this.program.programId
+        );
+    }
+
+    async userStatsAcc(authority: PublicKey) {
+        const pda = await this.userStats(authority);
+        return await this.program.account.userStats.fetch(pda);
+    }
+
+    async trustList(authority: PublicKey): Promise<PublicKey> {
+        return await findPDA(
+            [
+                Buffer.from("trust_list"),
+                authority.toBuffer(),
+            ],
+            this.program.programId
+        );
+    }
+
+    async trustListAcc(authority: PublicKey) {
+        const pda = await this.trustList(authority);
+        return await this.program.account.trustList.fetch(pda);
+    }
+
     async fctrATA(owner: PublicKey): Promise<TokenAccount> {
         return await findATA(this, owner, this.fctrMint);
     }
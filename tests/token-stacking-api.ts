@@ -51,7 +51,11 @@ export async function registerUser(
             fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
             bcdevVault: await ctx.userBcdevVault(userAuthority.publicKey),
             user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
             receipt: await ctx.receipt(userAuthority.publicKey),
+            grantHistory: await ctx.grantHistory(userAuthority.publicKey),
+            reputation: await ctx.reputation(userAuthority.publicKey),
+            trustList: await ctx.trustList(userAuthority.publicKey),
             authority: userAuthority.publicKey,
             solVault: ctx.solVault,
             systemProgram: SystemProgram.programId,
@@ -85,6 +89,7 @@ export async function buyTokens(
         .buyTokens(new BN(lamports))
         .accounts({
             user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
             fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
             solVault: ctx.solVault,
             platform: ctx.platform,
@@ -105,6 +110,7 @@ export async function sellFctrTokens(
         .sellFctrTokens()
         .accounts({
             user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
             fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
             solVault: ctx.solVault,
             platform: ctx.platform,
@@ -126,6 +132,7 @@ export async function sellBcdevTokens(
         .sellBcdevTokens(new BN(amount))
         .accounts({
             user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
             bcdevVault: await ctx.userBcdevVault(userAuthority.publicKey),
             solVault: ctx.solVault,
             platform: ctx.platform,
@@ -155,27 +162,31 @@ export async function startRound(
 
 export async function stake(
     ctx: Context,
-    userAuthority: Keypair
+    userAuthority: Keypair,
+    signer: Keypair = userAuthority
 ): Promise<void> {
     await ctx.program.methods
         .stake()
         .accounts({
             user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
             receipt: await ctx.receipt(userAuthority.publicKey),
             fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
             platform: ctx.platform,
             platformFctrTokenVault: await ctx.fctrVault(),
             authority: userAuthority.publicKey,
+            signer: signer.publicKey,
             systemProgram: SystemProgram.programId,
             tokenProgram: TOKEN_PROGRAM_ID
         })
-        .signers([userAuthority])
+        .signers([signer])
         .rpc();
 }
 
 export async function unstake(
     ctx: Context,
-    userAuthority: Keypair
+    userAuthority: Keypair,
+    signer: Keypair = userAuthority
 ): Promise<void> {
     const remainingAccounts = [];
     const receipt = await ctx.program.account.receipt.fetch(
@@ -198,6 +209,11 @@ export async function unstake(
                 pubkey: await ctx.userBcdevVault(ctx.users[i].publicKey),
                 isSigner: false,
                 isWritable: true,
+            },
+            {
+                pubkey: await ctx.userStats(ctx.users[i].publicKey),
+                isSigner: false,
+                isWritable: true,
             }
         );
     }
@@ -206,8 +222,12 @@ export async function unstake(
         .unstake()
         .accounts({
             receipt: await ctx.receipt(userAuthority.publicKey),
+            grantHistory: await ctx.grantHistory(userAuthority.publicKey),
+            reputation: await ctx.reputation(userAuthority.publicKey),
             user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
             authority: userAuthority.publicKey,
+            signer: signer.publicKey,
             fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
             bcdevVault: await ctx.userBcdevVault(userAuthority.publicKey),
             platform: ctx.platform,
@@ -217,7 +237,7 @@ export async function unstake(
             tokenProgram: TOKEN_PROGRAM_ID
         })
         .remainingAccounts(remainingAccounts)
-        .signers([userAuthority])
+        .signers([signer])
         .rpc();
 }
 
@@ -225,18 +245,25 @@ export async function grantTokens(
     ctx: Context,
     amount: number,
     confidantUser: PublicKey,
-    userAuthority: Keypair
+    userAuthority: Keypair,
+    grantorShareBps: number = 5_000
 ): Promise<void> {
     await ctx.program.methods
-        .grantTokens(new BN(amount))
+        .grantTokens(new BN(amount), grantorShareBps)
         .accounts({
             receipt: await ctx.receipt(userAuthority.publicKey),
             user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
+            confidantUserStats: await ctx.userStats(confidantUser),
             fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
             authority: userAuthority.publicKey,
             confidantUser: await ctx.user(confidantUser),
             confidantReceipt: await ctx.receipt(confidantUser),
+            confidantReputation: await ctx.reputation(confidantUser),
+            confidantGrantHistory: await ctx.grantHistory(confidantUser),
             confidantAuthority: confidantUser,
+            trustList: await ctx.trustList(userAuthority.publicKey),
+            confidantTrustList: await ctx.trustList(confidantUser),
             platform: ctx.platform,
             platformFctrTokenVault: await ctx.fctrVault(),
             systemProgram: SystemProgram.programId,
@@ -266,10 +293,204 @@ export async function withdraw(
 export async function claimTokens(
     ctx: Context,
     confidantUser: PublicKey,
-    userAuthority: Keypair
+    userAuthority: Keypair,
+    signer: Keypair = userAuthority
 ): Promise<void> {
     await ctx.program.methods
         .claimTokens()
+        .accounts({
+            receipt: await ctx.receipt(userAuthority.publicKey),
+            user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
+            fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            signer: signer.publicKey,
+            confidantUser: await ctx.user(confidantUser),
+            confidantReceipt: await ctx.receipt(confidantUser),
+            confidantAuthority: confidantUser,
+            platform: ctx.platform,
+            platformFctrTokenVault: await ctx.fctrVault(),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID
+        })
+        .signers([signer])
+        .rpc();
+}
+export async function openPosition(
+    ctx: Context,
+    amount: number,
+    lockRounds: number,
+    userAuthority: Keypair
+): Promise<void> {
+    const user = await ctx.userAcc(userAuthority.publicKey);
+    await ctx.program.methods
+        .openPosition(new BN(amount), lockRounds)
+        .accounts({
+            user: await ctx.user(userAuthority.publicKey),
+            position: await ctx.position(userAuthority.publicKey, user.nextPositionId),
+            fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            platform: ctx.platform,
+            platformFctrTokenVault: await ctx.fctrVault(),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID
+        })
+        .signers([userAuthority])
+        .rpc();
+}
+
+export async function closePosition(
+    ctx: Context,
+    id: number,
+    userAuthority: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .closePosition()
+        .accounts({
+            user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
+            position: await ctx.position(userAuthority.publicKey, id),
+            fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
+            bcdevVault: await ctx.userBcdevVault(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            platform: ctx.platform,
+            platformFctrTokenVault: await ctx.fctrVault(),
+            bcdevMint: ctx.bcdevMint,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID
+        })
+        .signers([userAuthority])
+        .rpc();
+}
+
+export async function setOperator(
+    ctx: Context,
+    operator: PublicKey | null,
+    userAuthority: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .setOperator(operator)
+        .accounts({
+            user: await ctx.user(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            platform: ctx.platform,
+        })
+        .signers([userAuthority])
+        .rpc();
+}
+
+export async function snapshot(
+    ctx: Context,
+    userAuthority: PublicKey,
+    payer: Keypair
+): Promise<void> {
+    const roundIndex = (await ctx.platformAcc()).roundIndex;
+    await ctx.program.methods
+        .snapshot()
+        .accounts({
+            platform: ctx.platform,
+            snapshot: await ctx.snapshot(roundIndex),
+            votingWeight: await ctx.votingWeight(roundIndex, userAuthority),
+            user: await ctx.user(userAuthority),
+            receipt: await ctx.receipt(userAuthority),
+            authority: userAuthority,
+            payer: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+}
+
+export async function proveVotingWeight(
+    ctx: Context,
+    roundIndex: number | BN,
+    userAuthority: PublicKey
+): Promise<void> {
+    await ctx.program.methods
+        .proveVotingWeight(new BN(roundIndex))
+        .accounts({
+            snapshot: await ctx.snapshot(roundIndex),
+            votingWeight: await ctx.votingWeight(roundIndex, userAuthority),
+            authority: userAuthority,
+        })
+        .rpc();
+}
+
+export interface TradeQuote {
+    amountIn: BN;
+    amountOut: BN;
+}
+
+export async function quoteBuy(ctx: Context, lamports: number | BN): Promise<TradeQuote> {
+    const simulation = await ctx.program.methods
+        .quoteBuy(new BN(lamports))
+        .accounts({})
+        .simulate();
+    return decodeReturnData(ctx, "TradeQuote", simulation.raw);
+}
+
+export async function quoteSellFctr(ctx: Context, amount: number | BN): Promise<TradeQuote> {
+    const simulation = await ctx.program.methods
+        .quoteSellFctr(new BN(amount))
+        .accounts({})
+        .simulate();
+    return decodeReturnData(ctx, "TradeQuote", simulation.raw);
+}
+
+export async function quoteSellBcdev(ctx: Context, amount: number | BN): Promise<TradeQuote> {
+    const simulation = await ctx.program.methods
+        .quoteSellBcdev(new BN(amount))
+        .accounts({})
+        .simulate();
+    return decodeReturnData(ctx, "TradeQuote", simulation.raw);
+}
+
+export async function projectReward(ctx: Context, userAuthority: PublicKey): Promise<any> {
+    const simulation = await ctx.program.methods
+        .projectReward()
+        .accounts({
+            receipt: await ctx.receipt(userAuthority),
+            user: await ctx.user(userAuthority),
+            authority: userAuthority,
+            platform: ctx.platform,
+        })
+        .simulate();
+    return decodeReturnData(ctx, "RewardProjection", simulation.raw);
+}
+
+export async function userSummary(ctx: Context, userAuthority: PublicKey): Promise<any> {
+    const simulation = await ctx.program.methods
+        .userSummary()
+        .accounts({
+            user: await ctx.user(userAuthority),
+            receipt: await ctx.receipt(userAuthority),
+            fctrVault: await ctx.userFctrVault(userAuthority),
+            bcdevVault: await ctx.userBcdevVault(userAuthority),
+            reputation: await ctx.reputation(userAuthority),
+            userStats: await ctx.userStats(userAuthority),
+            authority: userAuthority,
+            platform: ctx.platform,
+        })
+        .simulate();
+    return decodeReturnData(ctx, "UserSummary", simulation.raw);
+}
+
+function decodeReturnData(ctx: Context, typeName: string, logs: readonly string[]): any {
+    const prefix = `Program return: ${ctx.program.programId.toBase58()} `;
+    const line = logs.find(log => log.startsWith(prefix));
+    if (line === undefined) {
+        throw new Error(`No return data in the logs of ${typeName}`);
+    }
+    return ctx.program.coder.types.decode(typeName, Buffer.from(line.slice(prefix.length), "base64"));
+}
+
+export async function revokeGrant(
+    ctx: Context,
+    confidantUser: PublicKey,
+    userAuthority: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .revokeGrant()
         .accounts({
             receipt: await ctx.receipt(userAuthority.publicKey),
             user: await ctx.user(userAuthority.publicKey),
@@ -277,6 +498,8 @@ export async function claimTokens(
             authority: userAuthority.publicKey,
             confidantUser: await ctx.user(confidantUser),
             confidantReceipt: await ctx.receipt(confidantUser),
+            confidantReputation: await ctx.reputation(confidantUser),
+            confidantGrantHistory: await ctx.grantHistory(confidantUser),
             confidantAuthority: confidantUser,
             platform: ctx.platform,
             platformFctrTokenVault: await ctx.fctrVault(),
@@ -285,4 +508,244 @@ export async function claimTokens(
         })
         .signers([userAuthority])
         .rpc();
-}
\ No newline at end of file
+}
+
+export async function updateGrantLimits(
+    ctx: Context,
+    maxActiveGrantors: number,
+    maxGrantorsHistory: number,
+    minGrantRatio: number,
+    maxGrantRatio: number,
+    authority: Keypair = ctx.platformAuthority
+): Promise<void> {
+    await ctx.program.methods
+        .updateGrantLimits(maxActiveGrantors, maxGrantorsHistory, minGrantRatio, maxGrantRatio)
+        .accounts({
+            platform: ctx.platform,
+            authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+}
+
+export async function resizeReceipt(
+    ctx: Context,
+    userAuthority: PublicKey,
+    payer: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .resizeReceipt()
+        .accounts({
+            platform: ctx.platform,
+            user: await ctx.user(userAuthority),
+            receipt: await ctx.receipt(userAuthority),
+            grantHistory: await ctx.grantHistory(userAuthority),
+            authority: userAuthority,
+            payer: payer.publicKey,
+            systemProgram: SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+}
+
+export async function updateReclaimGracePeriod(
+    ctx: Context,
+    gracePeriod: number
+): Promise<void> {
+    await ctx.program.methods
+        .updateReclaimGracePeriod(new BN(gracePeriod))
+        .accounts({
+            platform: ctx.platform,
+            authority: ctx.platformAuthority.publicKey,
+        })
+        .signers([ctx.platformAuthority])
+        .rpc();
+}
+
+export async function reclaimGrant(
+    ctx: Context,
+    confidantUser: PublicKey,
+    userAuthority: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .reclaimGrant()
+        .accounts({
+            user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
+            fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
+            bcdevVault: await ctx.userBcdevVault(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            confidantUser: await ctx.user(confidantUser),
+            confidantReceipt: await ctx.receipt(confidantUser),
+            confidantReputation: await ctx.reputation(confidantUser),
+            confidantAuthority: confidantUser,
+            platform: ctx.platform,
+            platformFctrTokenVault: await ctx.fctrVault(),
+            bcdevMint: ctx.bcdevMint,
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID
+        })
+        .signers([userAuthority])
+        .rpc();
+}
+
+export async function offerGrant(
+    ctx: Context,
+    amount: number,
+    confidantUser: PublicKey,
+    userAuthority: Keypair,
+    grantorShareBps: number = 5_000
+): Promise<void> {
+    await ctx.program.methods
+        .offerGrant(new BN(amount), grantorShareBps)
+        .accounts({
+            user: await ctx.user(userAuthority.publicKey),
+            fctrVault: await ctx.userFctrVault(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            confidantUser: await ctx.user(confidantUser),
+            confidantAuthority: confidantUser,
+            trustList: await ctx.trustList(userAuthority.publicKey),
+            confidantTrustList: await ctx.trustList(confidantUser),
+            grantOffer: await ctx.grantOffer(userAuthority.publicKey, confidantUser),
+            platform: ctx.platform,
+            platformFctrTokenVault: await ctx.fctrVault(),
+            systemProgram: SystemProgram.programId,
+            tokenProgram: TOKEN_PROGRAM_ID
+        })
+        .signers([userAuthority])
+        .rpc();
+}
+
+export async function acceptGrant(
+    ctx: Context,
+    grantorUser: PublicKey,
+    userAuthority: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .acceptGrant()
+        .accounts({
+            user: await ctx.user(userAuthority.publicKey),
+            userStats: await ctx.userStats(userAuthority.publicKey),
+            grantorUserStats: await ctx.userStats(grantorUser),
+            receipt: await ctx.receipt(userAuthority.publicKey),
+            grantHistory: await ctx.grantHistory(userAuthority.publicKey),
+            reputation: await ctx.reputation(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            grantorUser: await ctx.user(grantorUser),
+            grantorReceipt: await ctx.receipt(grantorUser),
+            grantorFctrVault: await ctx.userFctrVault(grantorUser),
+            grantorAuthority: grantorUser,
+            grantOffer: await ctx.grantOffer(grantorUser, userAuthority.publicKey),
+            trustList: await ctx.trustList(userAuthority.publicKey),
+            grantorTrustList: await ctx.trustList(grantorUser),
+            platform: ctx.platform,
+        })
+        .signers([userAuthority])
+        .rpc();
+}
+
+export async function rejectGrant(
+    ctx: Context,
+    grantorUser: PublicKey,
+    confidantUser: PublicKey,
+    signer: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .rejectGrant()
+        .accounts({
+            grantorUser: await ctx.user(grantorUser),
+            grantorFctrVault: await ctx.userFctrVault(grantorUser),
+            grantorAuthority: grantorUser,
+            confidantUser: await ctx.user(confidantUser),
+            confidantAuthority: confidantUser,
+            grantOffer: await ctx.grantOffer(grantorUser, confidantUser),
+            signer: signer.publicKey,
+            platform: ctx.platform,
+            platformFctrTokenVault: await ctx.fctrVault(),
+            tokenProgram: TOKEN_PROGRAM_ID
+        })
+        .signers([signer])
+        .rpc();
+}
+
+export async function updateGrantOfferTtl(
+    ctx: Context,
+    ttlRounds: number
+): Promise<void> {
+    await ctx.program.methods
+        .updateGrantOfferTtl(new BN(ttlRounds))
+        .accounts({
+            platform: ctx.platform,
+            authority: ctx.platformAuthority.publicKey,
+        })
+        .signers([ctx.platformAuthority])
+        .rpc();
+}
+
+export async function updateGrantorShareBounds(
+    ctx: Context,
+    minGrantorShareBps: number,
+    maxGrantorShareBps: number
+): Promise<void> {
+    await ctx.program.methods
+        .updateGrantorShareBounds(minGrantorShareBps, maxGrantorShareBps)
+        .accounts({
+            platform: ctx.platform,
+            authority: ctx.platformAuthority.publicKey,
+        })
+        .signers([ctx.platformAuthority])
+        .rpc();
+}
+
+export async function updateTrustList(
+    ctx: Context,
+    allowListOnly: boolean,
+    entries: { user: PublicKey, allowed: boolean }[],
+    userAuthority: Keypair
+): Promise<void> {
+    const mode = allowListOnly ? {allowListOnly: {}} : {open: {}};
+    await ctx.program.methods
+        .updateTrustList(mode, entries)
+        .accounts({
+            user: await ctx.user(userAuthority.publicKey),
+            trustList: await ctx.trustList(userAuthority.publicKey),
+            authority: userAuthority.publicKey,
+            platform: ctx.platform,
+        })
+        .signers([userAuthority])
+        .rpc();
+}
+
+export async function auditPlatform(
+    ctx: Context,
+    autoPause: boolean,
+    auditor: Keypair
+): Promise<void> {
+    await ctx.program.methods
+        .auditPlatform(autoPause)
+        .accounts({
+            platform: ctx.platform,
+            fctrMint: ctx.fctrMint,
+            bcdevMint: ctx.bcdevMint,
+            platformFctrTokenVault: await ctx.fctrVault(),
+            solVault: ctx.solVault,
+            auditor: auditor.publicKey,
+        })
+        .signers([auditor])
+        .rpc();
+}
+
+export async function setPaused(
+    ctx: Context,
+    isPaused: boolean,
+    authority: Keypair = ctx.platformAuthority
+): Promise<void> {
+    await ctx.program.methods
+        .setPaused(isPaused)
+        .accounts({
+            platform: ctx.platform,
+            authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+}